@@ -0,0 +1,956 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate ring;
+
+use std::fmt;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use self::ring::constant_time::verify_slices_are_equal;
+use self::ring::rand::{SecureRandom, SystemRandom};
+
+use super::response::{ApiError, AuthError, TwitchResult};
+use super::TwitchClient;
+use urlparse::{parse_qs, quote, Query};
+
+mod oidc;
+mod scope;
+mod store;
+
+pub use self::oidc::{claims_param, verify_id_token, Claim, IdTokenClaims, Jwk, Jwks, ISSUER};
+pub use self::scope::{ParseScopeError, Scope, ScopeSet};
+pub(crate) use self::store::refresh_in_store;
+pub use self::store::{refresh_stored, FileTokenStore, MemoryTokenStore, TokenKey, TokenStore};
+
+const AUTHORIZE_URL: &'static str = "https://id.twitch.tv/oauth2/authorize";
+const DEVICE_URL: &'static str = "https://id.twitch.tv/oauth2/device";
+const TOKEN_URL: &'static str = "https://id.twitch.tv/oauth2/token";
+const DEVICE_GRANT_TYPE: &'static str = "urn:ietf:params:oauth:grant-type:device_code";
+
+fn gen_auth_url(
+    c: &TwitchClient,
+    rtype: &str,
+    redirect_url: &str,
+    scope: &[Scope],
+    state: &str,
+) -> String {
+    gen_auth_url_with(c, rtype, redirect_url, scope, state, &[])
+}
+
+fn gen_auth_url_with(
+    c: &TwitchClient,
+    rtype: &str,
+    redirect_url: &str,
+    scope: &[Scope],
+    state: &str,
+    extra: &[(&str, &str)],
+) -> String {
+    let scope = join_scope(scope);
+    let mut params = vec![
+        ("response_type", rtype),
+        ("client_id", c.cid.as_str()),
+        ("redirect_uri", redirect_url),
+        ("scope", &scope),
+        ("state", state),
+    ];
+    params.extend_from_slice(extra);
+
+    let query = params
+        .iter()
+        .map(|&(k, v)| format!("{}={}", k, quote(v, b"").unwrap()))
+        .collect::<Vec<String>>()
+        .join("&");
+    format!("{}?{}", AUTHORIZE_URL, query)
+}
+
+pub fn auth_code_flow(
+    c: &TwitchClient,
+    redirect_url: &str,
+    scope: &[Scope],
+    state: &str,
+) -> String {
+    gen_auth_url(c, "code", redirect_url, scope, state)
+}
+
+pub fn imp_grant_flow(
+    c: &TwitchClient,
+    redirect_url: &str,
+    scope: &[Scope],
+    state: &str,
+) -> String {
+    gen_auth_url(c, "token", redirect_url, scope, state)
+}
+
+/// Builds an authorize URL with a random `state` parameter
+///
+/// The resulting `PendingAuth` keeps the generated state so
+/// the redirect can be checked with `verify_callback`.
+///
+/// # Example
+///
+/// ```ignore
+/// let pending = AuthRequest::new(&c, ResponseType::Code, "http://localhost:3000")
+///     .scope(&[Scope::UserRead])
+///     .force_verify(true)
+///     .build()?;
+/// // send the user to pending.url(), then on redirect:
+/// let code = pending.verify_callback(&query)?;
+/// ```
+///
+/// Calling `openid` turns the request into an OpenID Connect
+/// request: the `openid` scope is added along with a random
+/// `nonce`, which `PendingAuth::verify_id_token` checks.
+///
+#[derive(Debug)]
+pub struct AuthRequest<'c> {
+    client: &'c TwitchClient,
+    response_type: ResponseType,
+    redirect_url: String,
+    scope: Vec<Scope>,
+    force_verify: bool,
+    openid: Option<String>,
+}
+
+impl<'c> AuthRequest<'c> {
+    pub fn new(
+        c: &'c TwitchClient,
+        response_type: ResponseType,
+        redirect_url: &str,
+    ) -> AuthRequest<'c> {
+        AuthRequest {
+            client: c,
+            response_type: response_type,
+            redirect_url: String::from(redirect_url),
+            scope: Vec::new(),
+            force_verify: false,
+            openid: None,
+        }
+    }
+
+    pub fn scope(mut self, scope: &[Scope]) -> AuthRequest<'c> {
+        self.scope.extend_from_slice(scope);
+        self
+    }
+
+    /// Forces the user to re-approve the application even
+    /// if they have already authorized it
+    pub fn force_verify(mut self, force_verify: bool) -> AuthRequest<'c> {
+        self.force_verify = force_verify;
+        self
+    }
+
+    /// Requests an ID token along with the given optional claims,
+    /// for the ID token itself and for the UserInfo endpoint
+    pub fn openid(mut self, id_token: &[Claim], userinfo: &[Claim]) -> AuthRequest<'c> {
+        if !self.scope.contains(&Scope::OpenId) {
+            self.scope.push(Scope::OpenId);
+        }
+        self.openid = Some(claims_param(id_token, userinfo));
+        self
+    }
+
+    pub fn build(self) -> TwitchResult<PendingAuth> {
+        let state = r#try!(gen_state());
+        let nonce = match self.openid {
+            Some(_) => Some(r#try!(gen_state())),
+            None => None,
+        };
+        let mut extra = Vec::new();
+        if self.force_verify {
+            extra.push(("force_verify", "true"));
+        }
+        if let Some(ref nonce) = nonce {
+            extra.push(("nonce", nonce.as_str()));
+        }
+        if let Some(ref claims) = self.openid {
+            if claims != "{}" {
+                extra.push(("claims", claims.as_str()));
+            }
+        }
+        let url = gen_auth_url_with(
+            self.client,
+            &self.response_type.to_string(),
+            &self.redirect_url,
+            &self.scope,
+            &state,
+            &extra,
+        );
+        Ok(PendingAuth {
+            url: url,
+            state: state,
+            nonce: nonce,
+            response_type: self.response_type,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResponseType {
+    /// Authorization code grant flow
+    Code,
+    /// Implicit grant flow
+    Token,
+}
+
+impl fmt::Display for ResponseType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ResponseType::Code => write!(f, "code"),
+            ResponseType::Token => write!(f, "token"),
+        }
+    }
+}
+
+/// An authorization request waiting for its redirect
+#[derive(Debug)]
+pub struct PendingAuth {
+    url: String,
+    state: String,
+    nonce: Option<String>,
+    response_type: ResponseType,
+}
+
+impl PendingAuth {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// The nonce the ID token has to carry, for OpenID requests
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
+    /// Verifies an ID token received for this request
+    ///
+    /// Fails with an `invalid_id_token` `AuthError` if the request
+    /// was not an OpenID request.
+    ///
+    pub fn verify_id_token(
+        &self,
+        c: &TwitchClient,
+        keys: &Jwks,
+        id_token: &str,
+    ) -> TwitchResult<IdTokenClaims> {
+        match self.nonce {
+            Some(ref nonce) => verify_id_token(c, keys, id_token, Some(nonce)),
+            None => Err(ApiError::from(AuthError {
+                error: "invalid_id_token".to_owned(),
+                description: "The request did not ask for an ID token".to_owned(),
+            })),
+        }
+    }
+
+    /// Checks the redirect Twitch sent back and extracts its result
+    ///
+    /// Accepts the query string of an authorization code redirect
+    /// or the fragment of an implicit grant redirect, with or
+    /// without the rest of the URL in front of it.
+    ///
+    pub fn verify_callback(&self, query: &str) -> TwitchResult<AuthResponse> {
+        let query = match query.find(|c| c == '?' || c == '#') {
+            Some(i) => &query[i + 1..],
+            None => query,
+        };
+        verify_redirect(&parse_qs(query), &self.state, self.response_type)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AuthResponse {
+    Code(String),
+    Token(String),
+}
+
+fn gen_state() -> TwitchResult<String> {
+    let mut buf = [0u8; 16];
+    r#try!(SystemRandom::new().fill(&mut buf).map_err(|_| AuthError {
+        error: "rng_failure".to_owned(),
+        description: "Failed to generate a random state".to_owned(),
+    }));
+    Ok(buf.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// How long a connection to the loopback listener may take
+/// to send its request line and headers
+const REDIRECT_READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Runs the authorization code flow against a local loopback listener
+///
+/// Listens on `127.0.0.1:<port>` and, where IPv6 is available,
+/// `[::1]:<port>`, hands the authorize URL to `open` so it can
+/// be shown to the user or launched in a browser, and waits up
+/// to `timeout` for Twitch to redirect back to
+/// `http://localhost:<port>`. Pass port `0` to let the OS pick
+/// a free port.
+///
+/// Returns the authorization code once the `state` parameter
+/// of the redirect has been checked.
+///
+pub fn loopback_code_flow<F>(
+    c: &TwitchClient,
+    port: u16,
+    scope: &[Scope],
+    state: &str,
+    timeout: Duration,
+    open: F,
+) -> TwitchResult<String>
+where
+    F: FnOnce(&str),
+{
+    let v4 = r#try!(TcpListener::bind(("127.0.0.1", port)));
+    let port = r#try!(v4.local_addr()).port();
+    let mut listeners = vec![v4];
+    // browsers may resolve localhost to ::1 instead
+    if let Ok(v6) = TcpListener::bind(("::1", port)) {
+        listeners.push(v6);
+    }
+    let redirect_url = format!("http://localhost:{}", port);
+    open(&auth_code_flow(c, &redirect_url, scope, state));
+    wait_for_redirect(&listeners, state, timeout)
+}
+
+fn wait_for_redirect(
+    listeners: &[TcpListener],
+    state: &str,
+    timeout: Duration,
+) -> TwitchResult<String> {
+    let deadline = Instant::now() + timeout;
+    for listener in listeners {
+        r#try!(listener.set_nonblocking(true));
+    }
+
+    loop {
+        let mut accepted = false;
+        for listener in listeners {
+            let mut stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Err(ApiError::from(e)),
+            };
+            accepted = true;
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(ApiError::timeout());
+            }
+            // preconnects and speculative sockets may never send a
+            // request, so a failed read only drops that connection
+            let read_timeout = std::cmp::min(REDIRECT_READ_TIMEOUT, deadline - now);
+            let query = match stream
+                .set_nonblocking(false)
+                .and_then(|_| stream.set_read_timeout(Some(read_timeout)))
+                .and_then(|_| read_redirect_query(&stream))
+            {
+                Ok(Some(q)) => q,
+                // browsers also ask for things like /favicon.ico
+                Ok(None) => {
+                    let _ = write_page(&mut stream, "404 Not Found", "Not Found");
+                    continue;
+                }
+                Err(_) => continue,
+            };
+            let r = verify_redirect(&query, state, ResponseType::Code).map(|r| match r {
+                AuthResponse::Code(code) | AuthResponse::Token(code) => code,
+            });
+            let _ = match r {
+                Ok(_) => write_page(
+                    &mut stream,
+                    "200 OK",
+                    "Authorization complete. You can close this window.",
+                ),
+                Err(_) => write_page(
+                    &mut stream,
+                    "400 Bad Request",
+                    "Authorization failed. You can close this window.",
+                ),
+            };
+            return r;
+        }
+        if !accepted {
+            if Instant::now() >= deadline {
+                return Err(ApiError::timeout());
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+fn read_redirect_query(stream: &TcpStream) -> io::Result<Option<Query>> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    r#try!(reader.read_line(&mut request_line));
+    // drain the headers so the browser sees a clean response
+    let mut line = String::new();
+    while r#try!(reader.read_line(&mut line)) > 2 {
+        line.clear();
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("");
+    let query = match target.find('?') {
+        Some(i) => parse_qs(&target[i + 1..]),
+        None => return Ok(None),
+    };
+    if query.contains_key("code") || query.contains_key("error") || query.contains_key("state") {
+        Ok(Some(query))
+    } else {
+        Ok(None)
+    }
+}
+
+fn verify_redirect(
+    query: &Query,
+    state: &str,
+    response_type: ResponseType,
+) -> TwitchResult<AuthResponse> {
+    let get = |key: &str| query.get(key).and_then(|v| v.first()).cloned();
+
+    let received = get("state").unwrap_or_default();
+    if verify_slices_are_equal(received.as_bytes(), state.as_bytes()).is_err() {
+        return Err(ApiError::from(AuthError {
+            error: "state_mismatch".to_owned(),
+            description: "The state parameter of the redirect does not match".to_owned(),
+        }));
+    }
+    if let Some(error) = get("error") {
+        return Err(ApiError::from(AuthError {
+            error: error,
+            description: get("error_description").unwrap_or_default(),
+        }));
+    }
+    let r = match response_type {
+        ResponseType::Code => get("code").map(AuthResponse::Code),
+        ResponseType::Token => get("access_token").map(AuthResponse::Token),
+    };
+    match r {
+        Some(r) => Ok(r),
+        None => Err(ApiError::from(AuthError {
+            error: "missing_code".to_owned(),
+            description: format!("The redirect did not contain a {}", response_type),
+        })),
+    }
+}
+
+fn write_page(stream: &mut TcpStream, status: &str, body: &str) -> io::Result<()> {
+    let body = format!(
+        "<!DOCTYPE html><html><head><title>Twitch</title></head><body><p>{}</p></body></html>",
+        body
+    );
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Runs the OAuth device authorization grant flow
+///
+/// Requests a device code, hands it to `prompt` so the
+/// verification URI and user code can be shown to the
+/// user, then polls until the user has authorized the
+/// application on another device.
+///
+/// Returns `ApiError::Timeout` if the device code expires
+/// before the user responds.
+///
+pub fn device_code_flow<F>(
+    c: &TwitchClient,
+    scope: &[Scope],
+    prompt: F,
+) -> TwitchResult<AccessToken>
+where
+    F: FnOnce(&DeviceCode),
+{
+    let code = r#try!(request_device_code(c, scope));
+    prompt(&code);
+    poll_device_token(c, scope, &code)
+}
+
+/// Requests a device code and user code for the device flow
+///
+pub fn request_device_code(c: &TwitchClient, scope: &[Scope]) -> TwitchResult<DeviceCode> {
+    let scopes = join_scope(scope);
+    let r = r#try!(
+        c.post_form::<DeviceCode>(DEVICE_URL, &[("client_id", &c.cid), ("scopes", &scopes)])
+    );
+    Ok(r)
+}
+
+/// Polls the token endpoint until the device code is authorized
+///
+/// Waits `interval` seconds between attempts and backs off
+/// by another five seconds whenever Twitch answers `slow_down`.
+///
+pub fn poll_device_token(
+    c: &TwitchClient,
+    scope: &[Scope],
+    code: &DeviceCode,
+) -> TwitchResult<AccessToken> {
+    let scopes = join_scope(scope);
+    let params = [
+        ("client_id", c.cid.as_str()),
+        ("scopes", &scopes),
+        ("device_code", &code.device_code),
+        ("grant_type", DEVICE_GRANT_TYPE),
+    ];
+    let deadline = Instant::now() + Duration::from_secs(code.expires_in);
+    let mut interval = Duration::from_secs(code.interval);
+
+    loop {
+        thread::sleep(interval);
+        match c.post_form::<AccessToken>(TOKEN_URL, &params) {
+            Ok(r) => return Ok(r),
+            Err(e) => match device_poll(&e) {
+                Some(DevicePoll::Pending) => (),
+                Some(DevicePoll::SlowDown) => interval += Duration::from_secs(5),
+                None => return Err(e),
+            },
+        }
+        if Instant::now() + interval >= deadline {
+            return Err(ApiError::timeout());
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum DevicePoll {
+    Pending,
+    SlowDown,
+}
+
+/// Whether a token request failed only because the user has
+/// not authorized the device code yet
+fn device_poll(e: &ApiError) -> Option<DevicePoll> {
+    match *e {
+        ApiError::TwitchError(ref e) if e.message == "authorization_pending" => {
+            Some(DevicePoll::Pending)
+        }
+        ApiError::TwitchError(ref e) if e.message == "slow_down" => Some(DevicePoll::SlowDown),
+        _ => None,
+    }
+}
+
+/// Exchanges an authorization code for a token pair
+///
+/// The response carries an `id_token` if the `openid`
+/// scope was requested.
+///
+pub fn exchange_code(
+    c: &TwitchClient,
+    client_secret: &str,
+    code: &str,
+    redirect_url: &str,
+) -> TwitchResult<AccessToken> {
+    let r = r#try!(c.post_form::<AccessToken>(
+        TOKEN_URL,
+        &[
+            ("client_id", &c.cid),
+            ("client_secret", client_secret),
+            ("code", code),
+            ("grant_type", "authorization_code"),
+            ("redirect_uri", redirect_url),
+        ]
+    ));
+    Ok(r)
+}
+
+/// Exchanges a refresh token for a new token pair
+///
+/// See `refresh_stored` to have the new pair written back
+/// to a `TokenStore`.
+///
+pub fn refresh_token(
+    c: &TwitchClient,
+    client_secret: &str,
+    refresh_token: &str,
+) -> TwitchResult<AccessToken> {
+    let r = r#try!(c.post_form::<AccessToken>(
+        TOKEN_URL,
+        &[
+            ("client_id", &c.cid),
+            ("client_secret", client_secret),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ]
+    ));
+    Ok(r)
+}
+
+fn join_scope(scopes: &[Scope]) -> String {
+    scopes
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+///////////////////////////////////////
+// DeviceCode
+///////////////////////////////////////
+#[derive(Deserialize, Debug)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub expires_in: u64,
+    pub interval: u64,
+    pub user_code: String,
+    pub verification_uri: String,
+}
+
+///////////////////////////////////////
+// AccessToken
+///////////////////////////////////////
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AccessToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_in: Option<u64>,
+    #[serde(default)]
+    pub scope: Vec<String>,
+    pub token_type: String,
+    pub id_token: Option<String>,
+}
+
+impl AccessToken {
+    /// The granted scopes, skipping any this crate does not know about
+    pub fn scopes(&self) -> ScopeSet {
+        ScopeSet::from_strs(&self.scope)
+    }
+}
+
+///////////////////////////////////////
+// ValidatedToken
+///////////////////////////////////////
+/// What Twitch reports about a token on validation
+///
+/// `login` and `user_id` are only set for user access tokens.
+#[derive(Debug, Clone)]
+pub struct ValidatedToken {
+    pub client_id: String,
+    pub login: Option<String>,
+    pub user_id: Option<String>,
+    pub scopes: ScopeSet,
+    pub expires_in: i64,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct SerdeValidatedToken {
+    client_id: String,
+    login: Option<String>,
+    user_id: Option<String>,
+    scopes: Option<Vec<String>>,
+    expires_in: i64,
+}
+
+impl From<SerdeValidatedToken> for ValidatedToken {
+    fn from(t: SerdeValidatedToken) -> ValidatedToken {
+        ValidatedToken {
+            client_id: t.client_id,
+            login: t.login,
+            user_id: t.user_id,
+            scopes: ScopeSet::from_strs(t.scopes.unwrap_or_default()),
+            expires_in: t.expires_in,
+        }
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+    extern crate urlparse;
+
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use self::urlparse::urlparse;
+
+    use super::super::new;
+    use super::super::response::{ApiError, ErrorResponse};
+    use super::super::tests::CLIENTID;
+    use super::{AuthRequest, AuthResponse, DeviceCode, DevicePoll, ResponseType, Scope};
+
+    fn redirect(auth_url: &str, query: &str) -> thread::JoinHandle<String> {
+        let params = urlparse(auth_url).get_parsed_query().unwrap();
+        let port = urlparse(&params["redirect_uri"][0]).port.unwrap();
+        let query = query.replace("{state}", &params["state"][0]);
+        thread::spawn(move || {
+            let mut s = TcpStream::connect(("127.0.0.1", port)).unwrap();
+            write!(s, "GET /?{} HTTP/1.1\r\nHost: localhost\r\n\r\n", query).unwrap();
+            let mut page = String::new();
+            s.read_to_string(&mut page).unwrap();
+            page
+        })
+    }
+
+    #[test]
+    fn loopback_code_flow() {
+        let c = new(String::from(CLIENTID));
+        let mut client = None;
+
+        let r = super::loopback_code_flow(
+            &c,
+            0,
+            &[Scope::UserRead],
+            "s3cr3t",
+            Duration::from_secs(10),
+            |url| client = Some(redirect(url, "code=abc123&scope=user_read&state={state}")),
+        );
+        assert_eq!(r.unwrap(), "abc123");
+        assert!(client
+            .unwrap()
+            .join()
+            .unwrap()
+            .starts_with("HTTP/1.1 200 OK"));
+    }
+
+    #[test]
+    fn loopback_state_mismatch() {
+        let c = new(String::from(CLIENTID));
+        let mut client = None;
+
+        let r = super::loopback_code_flow(
+            &c,
+            0,
+            &[Scope::UserRead],
+            "s3cr3t",
+            Duration::from_secs(10),
+            |url| client = Some(redirect(url, "code=abc123&state=forged")),
+        );
+        match r {
+            Err(ApiError::AuthError(e)) => assert_eq!(e.error, "state_mismatch"),
+            r => panic!("{:?}", r),
+        }
+        assert!(client.unwrap().join().unwrap().starts_with("HTTP/1.1 400"));
+    }
+
+    #[test]
+    fn loopback_denied() {
+        let c = new(String::from(CLIENTID));
+        let mut client = None;
+
+        let r = super::loopback_code_flow(
+            &c,
+            0,
+            &[Scope::UserRead],
+            "s3cr3t",
+            Duration::from_secs(10),
+            |url| {
+                client = Some(redirect(
+                    url,
+                    "error=access_denied&error_description=The+user+denied+you+access&state={state}",
+                ))
+            },
+        );
+        match r {
+            Err(ApiError::AuthError(e)) => {
+                assert_eq!(e.error, "access_denied");
+                assert_eq!(e.description, "The user denied you access");
+            }
+            r => panic!("{:?}", r),
+        }
+        client.unwrap().join().unwrap();
+    }
+
+    #[test]
+    fn loopback_timeout() {
+        let c = new(String::from(CLIENTID));
+
+        match super::loopback_code_flow(
+            &c,
+            0,
+            &[Scope::UserRead],
+            "s3cr3t",
+            Duration::from_millis(200),
+            |_| (),
+        ) {
+            Err(ApiError::Timeout(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn loopback_silent_connection() {
+        let c = new(String::from(CLIENTID));
+        let mut client = None;
+
+        let r = super::loopback_code_flow(
+            &c,
+            0,
+            &[Scope::UserRead],
+            "s3cr3t",
+            Duration::from_secs(10),
+            |url| {
+                let params = urlparse(url).get_parsed_query().unwrap();
+                let port = urlparse(&params["redirect_uri"][0]).port.unwrap();
+                // a preconnect that never sends a request
+                let silent = TcpStream::connect(("127.0.0.1", port)).unwrap();
+                client = Some((silent, redirect(url, "code=abc123&state={state}")));
+            },
+        );
+        assert_eq!(r.unwrap(), "abc123");
+        let (_silent, client) = client.unwrap();
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 200 OK"));
+    }
+
+    #[test]
+    fn loopback_silent_connection_timeout() {
+        let c = new(String::from(CLIENTID));
+        let mut silent = None;
+        let start = Instant::now();
+
+        let r = super::loopback_code_flow(
+            &c,
+            0,
+            &[Scope::UserRead],
+            "s3cr3t",
+            Duration::from_millis(500),
+            |url| {
+                let params = urlparse(url).get_parsed_query().unwrap();
+                let port = urlparse(&params["redirect_uri"][0]).port.unwrap();
+                silent = Some(TcpStream::connect(("127.0.0.1", port)).unwrap());
+            },
+        );
+        match r {
+            Err(ApiError::Timeout(_)) => (),
+            r => panic!("{:?}", r),
+        }
+        assert!(start.elapsed() < Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn join_scope() {
+        assert_eq!(
+            super::join_scope(&[Scope::UserRead, Scope::ChatLogin]),
+            "user_read chat_login"
+        );
+    }
+
+    #[test]
+    fn auth_url_encoding() {
+        let c = new(String::from("cid"));
+
+        assert_eq!(
+            super::auth_code_flow(
+                &c,
+                "http://localhost:3000/cb?x=1",
+                &[Scope::UserRead, Scope::ChatLogin],
+                "a&b"
+            ),
+            "https://id.twitch.tv/oauth2/authorize?response_type=code&client_id=cid\
+             &redirect_uri=http%3A%2F%2Flocalhost%3A3000%2Fcb%3Fx%3D1\
+             &scope=user_read%20chat_login&state=a%26b"
+        );
+    }
+
+    #[test]
+    fn auth_request() {
+        let c = new(String::from("cid"));
+        let a = AuthRequest::new(&c, ResponseType::Code, "http://localhost")
+            .scope(&[Scope::UserRead])
+            .force_verify(true)
+            .build()
+            .unwrap();
+        let b = AuthRequest::new(&c, ResponseType::Code, "http://localhost")
+            .build()
+            .unwrap();
+
+        assert!(a
+            .url()
+            .starts_with("https://id.twitch.tv/oauth2/authorize?response_type=code&"));
+        assert_eq!(a.state().len(), 32);
+        assert_ne!(a.state(), b.state());
+        assert!(a.url().contains(&format!("&state={}", a.state())));
+        assert!(a.url().ends_with("&force_verify=true"));
+        assert!(!b.url().contains("force_verify"));
+    }
+
+    #[test]
+    fn verify_callback() {
+        let c = new(String::from("cid"));
+        let code = AuthRequest::new(&c, ResponseType::Code, "http://localhost")
+            .build()
+            .unwrap();
+        let token = AuthRequest::new(&c, ResponseType::Token, "http://localhost")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            code.verify_callback(&format!("?code=abc&scope=user_read&state={}", code.state()))
+                .unwrap(),
+            AuthResponse::Code("abc".to_owned())
+        );
+        assert_eq!(
+            token
+                .verify_callback(&format!(
+                    "http://localhost/#access_token=xyz&scope=user_read&state={}&token_type=bearer",
+                    token.state()
+                ))
+                .unwrap(),
+            AuthResponse::Token("xyz".to_owned())
+        );
+        match code.verify_callback(&format!("code=abc&state={}", token.state())) {
+            Err(ApiError::AuthError(e)) => assert_eq!(e.error, "state_mismatch"),
+            r => panic!("{:?}", r),
+        }
+        match code.verify_callback(&format!(
+            "error=access_denied&error_description=denied&state={}",
+            code.state()
+        )) {
+            Err(ApiError::AuthError(e)) => assert_eq!(e.error, "access_denied"),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn device_code() {
+        let code: DeviceCode = serde_json::from_str(
+            r#"{"device_code":"ike3GM8QIdYZs43KdrWPIO36LofILoCyFEzjlQ91","expires_in":1800,"interval":5,"user_code":"ABCDEFGH","verification_uri":"https://www.twitch.tv/activate?public=true&device-code=ABCDEFGH"}"#,
+        )
+        .unwrap();
+        assert_eq!(code.user_code, "ABCDEFGH");
+        assert_eq!(code.interval, 5);
+        assert_eq!(code.expires_in, 1800);
+
+        let e = |s: &str| ApiError::from(serde_json::from_str::<ErrorResponse>(s).unwrap());
+        assert_eq!(
+            super::device_poll(&e(r#"{"status":400,"message":"authorization_pending"}"#)),
+            Some(DevicePoll::Pending)
+        );
+        assert_eq!(
+            super::device_poll(&e(r#"{"status":400,"message":"slow_down"}"#)),
+            Some(DevicePoll::SlowDown)
+        );
+        assert_eq!(
+            super::device_poll(&e(r#"{"status":400,"message":"invalid device code"}"#)),
+            None
+        );
+        assert_eq!(super::device_poll(&ApiError::timeout()), None);
+    }
+}
@@ -0,0 +1,391 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate base64;
+extern crate chrono;
+extern crate ring;
+extern crate serde_json;
+extern crate untrusted;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use self::chrono::prelude::*;
+use self::ring::signature;
+use self::untrusted::Input;
+
+use super::super::response::{ApiError, AuthError, TwitchResult};
+use super::super::TwitchClient;
+
+/// The `iss` claim of every ID token Twitch issues
+pub const ISSUER: &'static str = "https://id.twitch.tv/oauth2";
+const KEYS_URL: &'static str = "https://id.twitch.tv/oauth2/keys";
+/// Allowed clock skew when checking `exp`, in seconds
+const LEEWAY: i64 = 60;
+
+/// An optional claim that can be requested for the
+/// ID token or the UserInfo endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Claim {
+    Email,
+    EmailVerified,
+    Picture,
+    PreferredUsername,
+    UpdatedAt,
+}
+
+impl Claim {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Claim::Email => "email",
+            Claim::EmailVerified => "email_verified",
+            Claim::Picture => "picture",
+            Claim::PreferredUsername => "preferred_username",
+            Claim::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+/// Builds the `claims` parameter of an OIDC authorize URL
+///
+pub fn claims_param(id_token: &[Claim], userinfo: &[Claim]) -> String {
+    let mut claims: BTreeMap<&str, BTreeMap<&str, Option<()>>> = BTreeMap::new();
+    if !id_token.is_empty() {
+        claims.insert(
+            "id_token",
+            id_token.iter().map(|c| (c.as_str(), None)).collect(),
+        );
+    }
+    if !userinfo.is_empty() {
+        claims.insert(
+            "userinfo",
+            userinfo.iter().map(|c| (c.as_str(), None)).collect(),
+        );
+    }
+    serde_json::to_string(&claims).unwrap()
+}
+
+/// Verifies an ID token and returns its claims
+///
+/// Checks the RS256 signature against `keys`, that `iss` is
+/// Twitch, that `aud` is the client ID of `c`, that the token
+/// has not expired and, if given, that `nonce` matches the
+/// one sent with the authorize request.
+///
+pub fn verify_id_token(
+    c: &TwitchClient,
+    keys: &Jwks,
+    id_token: &str,
+    nonce: Option<&str>,
+) -> TwitchResult<IdTokenClaims> {
+    let parts: Vec<&str> = id_token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(invalid("The ID token is not a JWT"));
+    }
+    let header: SerdeJwtHeader = r#try!(serde_json::from_slice(&r#try!(decode(parts[0]))));
+    if header.alg != "RS256" {
+        return Err(invalid(&format!("Unsupported algorithm {}", header.alg)));
+    }
+    let key = r#try!(keys.find(header.kid.as_deref()));
+
+    let signed = &id_token[..parts[0].len() + 1 + parts[1].len()];
+    let (n, e) = (r#try!(decode(&key.n)), r#try!(decode(&key.e)));
+    let sig = r#try!(decode(parts[2]));
+    r#try!(signature::primitive::verify_rsa(
+        &signature::RSA_PKCS1_2048_8192_SHA256,
+        (Input::from(&n), Input::from(&e)),
+        Input::from(signed.as_bytes()),
+        Input::from(&sig),
+    )
+    .map_err(|_| invalid("The ID token signature is invalid")));
+
+    let claims: IdTokenClaims = r#try!(serde_json::from_slice(&r#try!(decode(parts[1]))));
+    if claims.iss != ISSUER {
+        return Err(invalid(&format!("Unexpected issuer {}", claims.iss)));
+    }
+    if claims.aud != c.cid {
+        return Err(invalid(&format!("Unexpected audience {}", claims.aud)));
+    }
+    if claims.exp + LEEWAY < UTC::now().timestamp() {
+        return Err(invalid("The ID token has expired"));
+    }
+    if let Some(nonce) = nonce {
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(invalid("The ID token nonce does not match"));
+        }
+    }
+    Ok(claims)
+}
+
+fn decode(segment: &str) -> TwitchResult<Vec<u8>> {
+    base64::decode_config(segment, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| invalid("The ID token is not valid base64url"))
+}
+
+fn invalid(description: &str) -> ApiError {
+    ApiError::from(AuthError {
+        error: "invalid_id_token".to_owned(),
+        description: description.to_owned(),
+    })
+}
+
+///////////////////////////////////////
+// IdTokenClaims
+///////////////////////////////////////
+/// The claims of a verified ID token
+///
+/// The optional claims are only present if they were requested.
+#[derive(Deserialize, Debug, Clone)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub azp: Option<String>,
+    pub nonce: Option<String>,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub picture: Option<String>,
+    pub preferred_username: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SerdeJwtHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+///////////////////////////////////////
+// Jwks
+///////////////////////////////////////
+/// A JSON Web Key Set holding the keys ID tokens are signed with
+#[derive(Deserialize, Debug, Clone)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Jwk {
+    pub kty: String,
+    pub kid: Option<String>,
+    pub alg: Option<String>,
+    #[serde(rename = "use")]
+    pub _use: Option<String>,
+    pub n: String,
+    pub e: String,
+}
+
+impl Jwks {
+    /// Fetches Twitch's current signing keys
+    pub fn fetch(c: &TwitchClient) -> TwitchResult<Jwks> {
+        let r = r#try!(c.get_url::<Jwks>(KEYS_URL));
+        Ok(r)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> TwitchResult<Jwks> {
+        let data = r#try!(fs::read(path));
+        let r = r#try!(serde_json::from_slice(&data));
+        Ok(r)
+    }
+
+    pub fn from_json(json: &str) -> TwitchResult<Jwks> {
+        let r = r#try!(serde_json::from_str(json));
+        Ok(r)
+    }
+
+    fn find(&self, kid: Option<&str>) -> TwitchResult<&Jwk> {
+        let key = match kid {
+            Some(kid) => self.keys.iter().find(|k| k.kid.as_deref() == Some(kid)),
+            None if self.keys.len() == 1 => self.keys.first(),
+            None => None,
+        };
+        match key {
+            Some(k) if k.kty == "RSA" => Ok(k),
+            Some(_) => Err(invalid("The signing key is not an RSA key")),
+            None => Err(invalid("No matching signing key")),
+        }
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use super::super::super::new;
+    use super::super::super::response::ApiError;
+    use super::super::{AuthRequest, ResponseType, Scope};
+    use super::{Claim, Jwks};
+
+    const JWKS: &'static str = concat!(
+        r#"{"keys":[{"alg":"RS256","e":"AQAB","kid":"1","kty":"RSA","use":"sig","n":""#,
+        "o3sod2rFHqtno7zS9frCbKm0P0DxN9rbKtZ0IFMbxI9a2SOm2wQTki4oQrUZ9-9nbkYGHDo-zAnelHzq\
+Nw6cX7N_yQMQZub5P6aAHVYYSyywMDck_WSRfZ7op7QHEYFDVAwG1rWIl0BPX7B834WZZdHnvwLP32at\
+ZxJ5WDOyh-pUoizgfsUNoYOc-0ND-3IvhBeU2fEMFlNRoKvB2awXwyHEn5vibXWWxQqaCu17PzXVogFl\
+y6GACUi_11SoTpWjO9-Z08nl21tFt7Y7ufZDCO5ujWT3W3TPoGEieVmGtn8ty3CGY9NGxh2MQn-6z03_\
+FDXF1nflCqK15sP1ojIMHQ",
+        r#""}]}"#
+    );
+
+    const VALID: &'static str =
+        "eyJhbGciOiJSUzI1NiIsImtpZCI6IjEiLCJ0eXAiOiJKV1QifQ.eyJhdWQiOiJ0ZXN0Y2xpZW50aWQiL\
+CJhenAiOiJ0ZXN0Y2xpZW50aWQiLCJlbWFpbCI6ImRldkBleGFtcGxlLmNvbSIsImVtYWlsX3Zlcmlma\
+WVkIjp0cnVlLCJleHAiOjQxMDI0NDQ4MDAsImlhdCI6MTcwMDAwMDAwMCwiaXNzIjoiaHR0cHM6Ly9pZ\
+C50d2l0Y2gudHYvb2F1dGgyIiwibm9uY2UiOiJhYmMxMjMiLCJwaWN0dXJlIjoiaHR0cHM6Ly9leGFtc\
+GxlLmNvbS9wLnBuZyIsInByZWZlcnJlZF91c2VybmFtZSI6InR3aXRjaGRldiIsInN1YiI6IjEyMzQif\
+Q.le3O5XgTAuszCgdkqbTegIJS4hFtMudaSyceogZj2eWUPoNCK7pdif8J3Fo-WG_uGaTzF-_UFkAblg\
+R_ql16Azbbx09lcdWRzj0Zk1npbSD0WGKkm2FYjuDo7Ab3zZm9urCxGanyeaGN9YwdtbhJhbWl13F5b1\
+swZOJJKRwT-SbT5I0WstMftj5ZfxXjeh3q7MFZMHnwsopl2bKSVUxWYLU18tULe9uEWowUYWSDbPnCoF\
+p5l7xWkBcNG9-wwv5FWjOoepQlxv5bOwzRfZm_rP2xNQ-5Y5hrJQmGj8tlIcdYXy9tI896W0uy3R4xUx\
+JYbzT6xguxrse7N_7Fnsc20Q";
+
+    const EXPIRED: &'static str =
+        "eyJhbGciOiJSUzI1NiIsImtpZCI6IjEiLCJ0eXAiOiJKV1QifQ.eyJhdWQiOiJ0ZXN0Y2xpZW50aWQiL\
+CJhenAiOiJ0ZXN0Y2xpZW50aWQiLCJlbWFpbCI6ImRldkBleGFtcGxlLmNvbSIsImVtYWlsX3Zlcmlma\
+WVkIjp0cnVlLCJleHAiOjE3MDAwMDAxMDAsImlhdCI6MTcwMDAwMDAwMCwiaXNzIjoiaHR0cHM6Ly9pZ\
+C50d2l0Y2gudHYvb2F1dGgyIiwibm9uY2UiOiJhYmMxMjMiLCJwaWN0dXJlIjoiaHR0cHM6Ly9leGFtc\
+GxlLmNvbS9wLnBuZyIsInByZWZlcnJlZF91c2VybmFtZSI6InR3aXRjaGRldiIsInN1YiI6IjEyMzQif\
+Q.Q4jxf0Yym8WzaDdYWdt7Jfh4bupkHDlLEcFH9TMRqir5vivH3Ot01cV5_WDwbqVcLeTtKz-3IgWDNp\
+NiX8HWP3nKfFpxEsAC5tDKNXv-l5-QLO0mBEI5DyDt9eEdPqmF6XtciLVIM_XpbC_u_pNwbi0-ksJT-2\
+GXg7CPeKC3pzdYLbjoRPxECw-mCTFt-l2hCqtALxOFWqMnISNkgSb8o7sRmNzw2_Ar_iNkk_JYM9uQto\
+NnT2lvAzfE_3xGx4io-eatxx86_yqF1Km2YsG4On_ahQSrf3Z86ZOSQIZo8X_9UNG1d1SptKUUf3pp4V\
+SluD6DC0MlezYPwD5PYiFjyA";
+
+    const OTHER_AUD: &'static str =
+        "eyJhbGciOiJSUzI1NiIsImtpZCI6IjEiLCJ0eXAiOiJKV1QifQ.eyJhdWQiOiJvdGhlcmNsaWVudCIsI\
+mF6cCI6InRlc3RjbGllbnRpZCIsImVtYWlsIjoiZGV2QGV4YW1wbGUuY29tIiwiZW1haWxfdmVyaWZpZ\
+WQiOnRydWUsImV4cCI6NDEwMjQ0NDgwMCwiaWF0IjoxNzAwMDAwMDAwLCJpc3MiOiJodHRwczovL2lkL\
+nR3aXRjaC50di9vYXV0aDIiLCJub25jZSI6ImFiYzEyMyIsInBpY3R1cmUiOiJodHRwczovL2V4YW1wb\
+GUuY29tL3AucG5nIiwicHJlZmVycmVkX3VzZXJuYW1lIjoidHdpdGNoZGV2Iiwic3ViIjoiMTIzNCJ9.\
+KORtzZYnjLPPGlLYsHBDGeoMPQn2AHlv2-LAB1wcavY80QyImJY_wpU3mFSkWNv8PEpYXU4qb-T0kH8o\
+ag-tNqVVq-fgASGDAXqqO_WjtxXPPBF5q2_kCP09aYpKuB4INr6Q3hrkaPjksXLVNa7DlJrS9aQ68DUh\
+Xb0NNd3DNHXkiYJtigmfqTa1YU9p_iREuV9aawAsHKggYFnIzMG2jDH8pa8lJgK0X58TsqYLqBDOxez-\
+YA5b787KcwJESNutisYI8lcP-uHgKNnBH6zS7PFIWuIfhCVDr0cpLs19jJyfkfiiC3L0A3WmmC3k7jsJ\
+W0DSLYPoazZ7kbKdSMMQDw";
+
+    fn assert_invalid<T: ::std::fmt::Debug>(r: Result<T, ApiError>, description: &str) {
+        match r {
+            Err(ApiError::AuthError(e)) => {
+                assert_eq!(e.error, "invalid_id_token");
+                assert!(e.description.contains(description), "{}", e.description);
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn claims_param() {
+        assert_eq!(
+            super::claims_param(&[Claim::Email, Claim::PreferredUsername], &[Claim::Picture]),
+            r#"{"id_token":{"email":null,"preferred_username":null},"userinfo":{"picture":null}}"#
+        );
+    }
+
+    #[test]
+    fn openid_request() {
+        let c = new(String::from("testclientid"));
+        let pending = AuthRequest::new(&c, ResponseType::Code, "http://localhost")
+            .scope(&[Scope::UserReadEmail])
+            .openid(&[Claim::Email], &[])
+            .build()
+            .unwrap();
+
+        let nonce = pending.nonce().unwrap();
+        assert_eq!(nonce.len(), 32);
+        assert!(pending
+            .url()
+            .starts_with("https://id.twitch.tv/oauth2/authorize?"));
+        assert!(pending
+            .url()
+            .contains("&scope=user%3Aread%3Aemail%20openid&"));
+        assert!(pending.url().contains(&format!("&nonce={}", nonce)));
+        assert!(pending
+            .url()
+            .contains("&claims=%7B%22id_token%22%3A%7B%22email%22%3Anull%7D%7D"));
+    }
+
+    #[test]
+    fn verify_id_token() {
+        let c = new(String::from("testclientid"));
+        let keys = Jwks::from_json(JWKS).unwrap();
+
+        let claims = super::verify_id_token(&c, &keys, VALID, Some("abc123")).unwrap();
+        assert_eq!(claims.sub, "1234");
+        assert_eq!(claims.preferred_username.unwrap(), "twitchdev");
+        assert_eq!(claims.picture.unwrap(), "https://example.com/p.png");
+        assert_eq!(claims.email.unwrap(), "dev@example.com");
+        assert_eq!(claims.email_verified, Some(true));
+        assert!(super::verify_id_token(&c, &keys, VALID, None).is_ok());
+
+        assert_invalid(
+            super::verify_id_token(&c, &keys, VALID, Some("other")),
+            "nonce",
+        );
+        assert_invalid(super::verify_id_token(&c, &keys, EXPIRED, None), "expired");
+        assert_invalid(
+            super::verify_id_token(&c, &keys, OTHER_AUD, None),
+            "audience",
+        );
+        assert_invalid(
+            super::verify_id_token(&new(String::from("other")), &keys, VALID, None),
+            "audience",
+        );
+    }
+
+    #[test]
+    fn verify_id_token_signature() {
+        let c = new(String::from("otherclient"));
+        let keys = Jwks::from_json(JWKS).unwrap();
+
+        // the claims of one token with the signature of another
+        let valid: Vec<&str> = VALID.split('.').collect();
+        let other: Vec<&str> = OTHER_AUD.split('.').collect();
+        let forged = format!("{}.{}.{}", other[0], other[1], valid[2]);
+        assert!(super::verify_id_token(&c, &keys, OTHER_AUD, None).is_ok());
+        assert_invalid(
+            super::verify_id_token(&c, &keys, &forged, None),
+            "signature",
+        );
+
+        let unknown_kid = Jwks::from_json(&JWKS.replace("\"kid\":\"1\"", "\"kid\":\"2\"")).unwrap();
+        assert_invalid(
+            super::verify_id_token(&c, &unknown_kid, OTHER_AUD, None),
+            "No matching signing key",
+        );
+        assert_invalid(super::verify_id_token(&c, &keys, "abc.def", None), "JWT");
+    }
+
+    #[test]
+    fn jwks_from_file() {
+        let path = env::temp_dir().join(format!("twitch_api-jwks-{}.json", process::id()));
+        fs::write(&path, JWKS).unwrap();
+        let keys = Jwks::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(keys.keys.len(), 1);
+        assert_eq!(keys.keys[0].kid.as_ref().unwrap(), "1");
+        assert_eq!(keys.keys[0]._use.as_ref().unwrap(), "sig");
+    }
+}
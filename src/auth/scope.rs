@@ -0,0 +1,434 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+use std::collections::btree_set;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+macro_rules! scopes {
+    ($($(#[$attr:meta])* $name:ident => $s:expr,)*) => {
+        /// An OAuth scope
+        ///
+        /// Converts to and from the string Twitch uses for it,
+        /// for example `Scope::ChatRead` is `"chat:read"`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum Scope {
+            $($(#[$attr])* $name,)*
+        }
+
+        impl Scope {
+            /// Every scope known to this crate
+            pub fn all() -> &'static [Scope] {
+                &[$(Scope::$name,)*]
+            }
+
+            pub fn as_str(&self) -> &'static str {
+                match *self {
+                    $(Scope::$name => $s,)*
+                }
+            }
+        }
+
+        impl FromStr for Scope {
+            type Err = ParseScopeError;
+
+            fn from_str(s: &str) -> Result<Scope, ParseScopeError> {
+                match s {
+                    $($s => Ok(Scope::$name),)*
+                    _ => Err(ParseScopeError {
+                        scope: String::from(s),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+scopes! {
+    // Kraken v5
+    ChannelCheckSubscription => "channel_check_subscription",
+    ChannelCommercial => "channel_commercial",
+    ChannelEditor => "channel_editor",
+    ChannelFeedEdit => "channel_feed_edit",
+    ChannelFeedRead => "channel_feed_read",
+    ChannelRead => "channel_read",
+    ChannelStream => "channel_stream",
+    ChannelSubscriptions => "channel_subscriptions",
+    ChatLogin => "chat_login",
+    CollectionsEdit => "collections_edit",
+    CommunitiesEdit => "communities_edit",
+    CommunitiesModerate => "communities_moderate",
+    UserBlocksEdit => "user_blocks_edit",
+    UserBlocksRead => "user_blocks_read",
+    UserFollowsEdit => "user_follows_edit",
+    UserRead => "user_read",
+    UserSubscriptions => "user_subscriptions",
+    ViewingActivityRead => "viewing_activity_read",
+
+    // OpenID Connect
+    OpenId => "openid",
+
+    // Helix
+    AnalyticsReadExtensions => "analytics:read:extensions",
+    AnalyticsReadGames => "analytics:read:games",
+    BitsRead => "bits:read",
+    ChannelBot => "channel:bot",
+    ChannelEditCommercial => "channel:edit:commercial",
+    ChannelManageAds => "channel:manage:ads",
+    ChannelManageBroadcast => "channel:manage:broadcast",
+    ChannelManageExtensions => "channel:manage:extensions",
+    ChannelManageGuestStar => "channel:manage:guest_star",
+    ChannelManageModerators => "channel:manage:moderators",
+    ChannelManagePolls => "channel:manage:polls",
+    ChannelManagePredictions => "channel:manage:predictions",
+    ChannelManageRaids => "channel:manage:raids",
+    ChannelManageRedemptions => "channel:manage:redemptions",
+    ChannelManageSchedule => "channel:manage:schedule",
+    ChannelManageVideos => "channel:manage:videos",
+    ChannelManageVips => "channel:manage:vips",
+    ChannelModerate => "channel:moderate",
+    ChannelReadAds => "channel:read:ads",
+    ChannelReadCharity => "channel:read:charity",
+    ChannelReadEditors => "channel:read:editors",
+    ChannelReadGoals => "channel:read:goals",
+    ChannelReadGuestStar => "channel:read:guest_star",
+    ChannelReadHypeTrain => "channel:read:hype_train",
+    ChannelReadPolls => "channel:read:polls",
+    ChannelReadPredictions => "channel:read:predictions",
+    ChannelReadRedemptions => "channel:read:redemptions",
+    ChannelReadStreamKey => "channel:read:stream_key",
+    ChannelReadSubscriptions => "channel:read:subscriptions",
+    ChannelReadVips => "channel:read:vips",
+    ChatEdit => "chat:edit",
+    ChatRead => "chat:read",
+    ClipsEdit => "clips:edit",
+    ModerationRead => "moderation:read",
+    ModeratorManageAnnouncements => "moderator:manage:announcements",
+    ModeratorManageAutomod => "moderator:manage:automod",
+    ModeratorManageAutomodSettings => "moderator:manage:automod_settings",
+    ModeratorManageBannedUsers => "moderator:manage:banned_users",
+    ModeratorManageBlockedTerms => "moderator:manage:blocked_terms",
+    ModeratorManageChatMessages => "moderator:manage:chat_messages",
+    ModeratorManageChatSettings => "moderator:manage:chat_settings",
+    ModeratorManageGuestStar => "moderator:manage:guest_star",
+    ModeratorManageShieldMode => "moderator:manage:shield_mode",
+    ModeratorManageShoutouts => "moderator:manage:shoutouts",
+    ModeratorManageUnbanRequests => "moderator:manage:unban_requests",
+    ModeratorManageWarnings => "moderator:manage:warnings",
+    ModeratorReadAutomodSettings => "moderator:read:automod_settings",
+    ModeratorReadBannedUsers => "moderator:read:banned_users",
+    ModeratorReadBlockedTerms => "moderator:read:blocked_terms",
+    ModeratorReadChatMessages => "moderator:read:chat_messages",
+    ModeratorReadChatSettings => "moderator:read:chat_settings",
+    ModeratorReadChatters => "moderator:read:chatters",
+    ModeratorReadFollowers => "moderator:read:followers",
+    ModeratorReadGuestStar => "moderator:read:guest_star",
+    ModeratorReadModerators => "moderator:read:moderators",
+    ModeratorReadShieldMode => "moderator:read:shield_mode",
+    ModeratorReadShoutouts => "moderator:read:shoutouts",
+    ModeratorReadSuspiciousUsers => "moderator:read:suspicious_users",
+    ModeratorReadUnbanRequests => "moderator:read:unban_requests",
+    ModeratorReadVips => "moderator:read:vips",
+    ModeratorReadWarnings => "moderator:read:warnings",
+    UserBot => "user:bot",
+    UserEdit => "user:edit",
+    UserEditBroadcast => "user:edit:broadcast",
+    UserEditFollows => "user:edit:follows",
+    UserManageBlockedUsers => "user:manage:blocked_users",
+    UserManageChatColor => "user:manage:chat_color",
+    UserManageWhispers => "user:manage:whispers",
+    UserReadBlockedUsers => "user:read:blocked_users",
+    UserReadBroadcast => "user:read:broadcast",
+    UserReadChat => "user:read:chat",
+    UserReadEmail => "user:read:email",
+    UserReadEmotes => "user:read:emotes",
+    UserReadFollows => "user:read:follows",
+    UserReadModeratedChannels => "user:read:moderated_channels",
+    UserReadSubscriptions => "user:read:subscriptions",
+    UserReadWhispers => "user:read:whispers",
+    UserWriteChat => "user:write:chat",
+    WhispersEdit => "whispers:edit",
+    WhispersRead => "whispers:read",
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Scope {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Deserialize for Scope {
+    fn deserialize<D>(deserializer: D) -> Result<Scope, D::Error>
+    where
+        D: Deserializer,
+    {
+        let s = r#try!(String::deserialize(deserializer));
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+///////////////////////////////////////
+// ScopeSet
+///////////////////////////////////////
+/// A set of OAuth scopes
+///
+/// Displays and parses as the space separated list
+/// used in OAuth requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ScopeSet {
+    scopes: BTreeSet<Scope>,
+}
+
+impl ScopeSet {
+    pub fn new() -> ScopeSet {
+        ScopeSet {
+            scopes: BTreeSet::new(),
+        }
+    }
+
+    /// Builds a set from scope strings, skipping any
+    /// that this crate does not know about
+    pub fn from_strs<I, S>(scopes: I) -> ScopeSet
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        scopes
+            .into_iter()
+            .filter_map(|s| s.as_ref().parse().ok())
+            .collect()
+    }
+
+    pub fn insert(&mut self, scope: Scope) -> bool {
+        self.scopes.insert(scope)
+    }
+
+    pub fn remove(&mut self, scope: Scope) -> bool {
+        self.scopes.remove(&scope)
+    }
+
+    pub fn contains(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    pub fn len(&self) -> usize {
+        self.scopes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    pub fn is_subset(&self, other: &ScopeSet) -> bool {
+        self.scopes.is_subset(&other.scopes)
+    }
+
+    pub fn union(&self, other: &ScopeSet) -> ScopeSet {
+        self.scopes.union(&other.scopes).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &ScopeSet) -> ScopeSet {
+        self.scopes.intersection(&other.scopes).cloned().collect()
+    }
+
+    /// Scopes in `self` that are not in `other`
+    pub fn difference(&self, other: &ScopeSet) -> ScopeSet {
+        self.scopes.difference(&other.scopes).cloned().collect()
+    }
+
+    /// Scopes from `required` that are not in this set
+    pub fn missing(&self, required: &[Scope]) -> ScopeSet {
+        required
+            .iter()
+            .filter(|s| !self.contains(**s))
+            .cloned()
+            .collect()
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, Scope> {
+        self.scopes.iter()
+    }
+
+    pub fn to_vec(&self) -> Vec<Scope> {
+        self.scopes.iter().cloned().collect()
+    }
+}
+
+impl FromIterator<Scope> for ScopeSet {
+    fn from_iter<I: IntoIterator<Item = Scope>>(iter: I) -> ScopeSet {
+        ScopeSet {
+            scopes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a [Scope]> for ScopeSet {
+    fn from(scopes: &'a [Scope]) -> ScopeSet {
+        scopes.iter().cloned().collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a ScopeSet {
+    type Item = &'a Scope;
+    type IntoIter = btree_set::Iter<'a, Scope>;
+
+    fn into_iter(self) -> btree_set::Iter<'a, Scope> {
+        self.scopes.iter()
+    }
+}
+
+impl fmt::Display for ScopeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for scope in &self.scopes {
+            if !first {
+                r#try!(f.write_str(" "));
+            }
+            r#try!(f.write_str(scope.as_str()));
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ScopeSet {
+    type Err = ParseScopeError;
+
+    /// Parses a list of scopes separated by spaces or `+`
+    fn from_str(s: &str) -> Result<ScopeSet, ParseScopeError> {
+        s.split(|c: char| c == '+' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+impl Serialize for ScopeSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = r#try!(serializer.serialize_seq(Some(self.len())));
+        for scope in &self.scopes {
+            r#try!(seq.serialize_element(scope));
+        }
+        seq.end()
+    }
+}
+
+impl Deserialize for ScopeSet {
+    fn deserialize<D>(deserializer: D) -> Result<ScopeSet, D::Error>
+    where
+        D: Deserializer,
+    {
+        let scopes = r#try!(Vec::<Scope>::deserialize(deserializer));
+        Ok(scopes.into_iter().collect())
+    }
+}
+
+///////////////////////////////////////
+// ParseScopeError
+///////////////////////////////////////
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseScopeError {
+    pub scope: String,
+}
+
+impl fmt::Display for ParseScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown scope \"{}\"", self.scope)
+    }
+}
+
+impl Error for ParseScopeError {
+    fn description(&self) -> &str {
+        "unknown scope"
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::{Scope, ScopeSet};
+
+    #[test]
+    fn round_trip() {
+        for scope in Scope::all() {
+            assert_eq!(scope.to_string().parse::<Scope>().unwrap(), *scope);
+        }
+        assert_eq!(
+            "moderator:manage:banned_users".parse::<Scope>().unwrap(),
+            Scope::ModeratorManageBannedUsers
+        );
+        assert_eq!(Scope::ViewingActivityRead.as_str(), "viewing_activity_read");
+        assert!("viewing_activity_ready".parse::<Scope>().is_err());
+    }
+
+    #[test]
+    fn serde() {
+        let s = serde_json::to_string(&Scope::ChatRead).unwrap();
+        assert_eq!(s, "\"chat:read\"");
+        assert_eq!(serde_json::from_str::<Scope>(&s).unwrap(), Scope::ChatRead);
+        assert!(serde_json::from_str::<Scope>("\"chat:write\"").is_err());
+
+        let set: ScopeSet = serde_json::from_str("[\"chat:read\",\"user_read\"]").unwrap();
+        assert!(set.contains(Scope::ChatRead) && set.contains(Scope::UserRead));
+        assert_eq!(
+            serde_json::to_string(&set).unwrap(),
+            "[\"user_read\",\"chat:read\"]"
+        );
+    }
+
+    #[test]
+    fn scope_set() {
+        let a: ScopeSet = "chat:read chat:edit+user:read:email".parse().unwrap();
+        let b = ScopeSet::from(&[Scope::ChatRead, Scope::ClipsEdit][..]);
+
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.to_string(), "chat:edit chat:read user:read:email");
+        assert_eq!(a.union(&b).len(), 4);
+        assert_eq!(a.intersection(&b).to_vec(), vec![Scope::ChatRead]);
+        assert_eq!(b.difference(&a).to_vec(), vec![Scope::ClipsEdit]);
+        assert_eq!(
+            a.missing(&[Scope::ChatRead, Scope::ClipsEdit]).to_vec(),
+            vec![Scope::ClipsEdit]
+        );
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!("chat:read bogus".parse::<ScopeSet>().is_err());
+        assert_eq!(ScopeSet::from_strs(&["chat:read", "bogus"]).len(), 1);
+    }
+}
@@ -0,0 +1,563 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate ring;
+extern crate serde_json;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use self::ring::aead::{open_in_place, seal_in_place, OpeningKey, SealingKey, CHACHA20_POLY1305};
+use self::ring::digest::SHA256;
+use self::ring::pbkdf2;
+use self::ring::rand::{SecureRandom, SystemRandom};
+
+use super::super::response::{ApiError, AuthError, TwitchResult};
+use super::super::TwitchClient;
+use super::{refresh_token, AccessToken};
+
+const MAGIC: &'static [u8] = b"TWTS\x01";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+/// A lock file older than this was left behind by a writer
+/// that died, writes take well under a second
+const STALE_LOCK: Duration = Duration::from_secs(30);
+
+/// Persistent storage for OAuth tokens
+///
+/// Implementations use interior mutability so one store
+/// can be shared between clients.
+pub trait TokenStore {
+    fn load(&self, key: &TokenKey) -> TwitchResult<Option<AccessToken>>;
+    fn save(&self, key: &TokenKey, token: &AccessToken) -> TwitchResult<()>;
+    fn delete(&self, key: &TokenKey) -> TwitchResult<()>;
+}
+
+/// Identifies a stored token
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TokenKey {
+    /// A user access token, keyed by user ID or login
+    User(String),
+    /// An app access token, keyed by client ID
+    App(String),
+}
+
+impl fmt::Display for TokenKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TokenKey::User(ref id) => write!(f, "user:{}", id),
+            TokenKey::App(ref id) => write!(f, "app:{}", id),
+        }
+    }
+}
+
+/// Refreshes the token stored under `key` and writes the new pair back
+///
+/// The client is switched over to the new access token as well.
+/// See `TwitchClient::set_token_store` to have this happen
+/// whenever Twitch rejects the token.
+///
+pub fn refresh_stored<S: TokenStore + ?Sized>(
+    c: &mut TwitchClient,
+    client_secret: &str,
+    store: &S,
+    key: &TokenKey,
+) -> TwitchResult<AccessToken> {
+    let new = r#try!(refresh_in_store(c, client_secret, store, key));
+    c.set_oauth_token(&new.access_token);
+    Ok(new)
+}
+
+/// Refreshes the token stored under `key` and saves the new pair
+pub(crate) fn refresh_in_store<S: TokenStore + ?Sized>(
+    c: &TwitchClient,
+    client_secret: &str,
+    store: &S,
+    key: &TokenKey,
+) -> TwitchResult<AccessToken> {
+    let old = match r#try!(store.load(key)) {
+        Some(t) => t,
+        None => {
+            return Err(ApiError::from(AuthError {
+                error: "missing_token".to_owned(),
+                description: format!("No token is stored for {}", key),
+            }))
+        }
+    };
+    let refresh = match old.refresh_token {
+        Some(ref r) => r.clone(),
+        None => {
+            return Err(ApiError::from(AuthError {
+                error: "missing_refresh_token".to_owned(),
+                description: format!("The token stored for {} cannot be refreshed", key),
+            }))
+        }
+    };
+
+    let new = r#try!(refresh_token(c, client_secret, &refresh));
+    r#try!(store.save(key, &new));
+    Ok(new)
+}
+
+///////////////////////////////////////
+// MemoryTokenStore
+///////////////////////////////////////
+/// Keeps tokens in memory for the lifetime of the process
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: Mutex<BTreeMap<TokenKey, AccessToken>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> MemoryTokenStore {
+        MemoryTokenStore::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self, key: &TokenKey) -> TwitchResult<Option<AccessToken>> {
+        Ok(self.tokens.lock().unwrap().get(key).cloned())
+    }
+
+    fn save(&self, key: &TokenKey, token: &AccessToken) -> TwitchResult<()> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(key.clone(), token.clone());
+        Ok(())
+    }
+
+    fn delete(&self, key: &TokenKey) -> TwitchResult<()> {
+        self.tokens.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+///////////////////////////////////////
+// FileTokenStore
+///////////////////////////////////////
+/// Keeps tokens in a JSON file
+///
+/// The file is replaced atomically on every write and created
+/// with `0600` permissions on Unix. Writers, also in other
+/// processes, take turns through a `<path>.lock` file, so stores
+/// sharing a file don't lose each other's tokens. With a passphrase the
+/// contents are encrypted with ChaCha20-Poly1305 under a key
+/// derived by PBKDF2-HMAC-SHA256.
+pub struct FileTokenStore {
+    path: PathBuf,
+    passphrase: Option<String>,
+    lock: Mutex<()>,
+}
+
+impl FileTokenStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> FileTokenStore {
+        FileTokenStore {
+            path: path.as_ref().to_path_buf(),
+            passphrase: None,
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn encrypted<P: AsRef<Path>>(path: P, passphrase: &str) -> FileTokenStore {
+        FileTokenStore {
+            path: path.as_ref().to_path_buf(),
+            passphrase: Some(String::from(passphrase)),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read_all(&self) -> TwitchResult<BTreeMap<String, AccessToken>> {
+        let mut data = Vec::new();
+        match fs::File::open(&self.path) {
+            Ok(mut f) => {
+                r#try!(f.read_to_end(&mut data));
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(ApiError::from(e)),
+        }
+        if let Some(ref passphrase) = self.passphrase {
+            data = r#try!(open(passphrase, &data));
+        }
+        let r = r#try!(serde_json::from_slice(&data));
+        Ok(r)
+    }
+
+    fn write_all(&self, tokens: &BTreeMap<String, AccessToken>) -> TwitchResult<()> {
+        let mut data = r#try!(serde_json::to_vec(tokens));
+        if let Some(ref passphrase) = self.passphrase {
+            data = r#try!(seal(passphrase, &data));
+        }
+
+        let tmp = r#try!(temp_path(&self.path));
+        let written = create_private(&tmp).and_then(|mut f| {
+            r#try!(f.write_all(&data));
+            f.sync_all()
+        });
+        if let Err(e) = written {
+            let _ = fs::remove_file(&tmp);
+            return Err(ApiError::from(e));
+        }
+        if let Err(e) = fs::rename(&tmp, &self.path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(ApiError::from(e));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FileTokenStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileTokenStore")
+            .field("path", &self.path)
+            .field(
+                "passphrase",
+                &self.passphrase.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self, key: &TokenKey) -> TwitchResult<Option<AccessToken>> {
+        let _guard = self.lock.lock().unwrap();
+        let mut tokens = r#try!(self.read_all());
+        Ok(tokens.remove(&key.to_string()))
+    }
+
+    fn save(&self, key: &TokenKey, token: &AccessToken) -> TwitchResult<()> {
+        let _guard = self.lock.lock().unwrap();
+        let _file_lock = r#try!(FileLock::acquire(&self.path));
+        let mut tokens = r#try!(self.read_all());
+        tokens.insert(key.to_string(), token.clone());
+        self.write_all(&tokens)
+    }
+
+    fn delete(&self, key: &TokenKey) -> TwitchResult<()> {
+        let _guard = self.lock.lock().unwrap();
+        let _file_lock = r#try!(FileLock::acquire(&self.path));
+        let mut tokens = r#try!(self.read_all());
+        if tokens.remove(&key.to_string()).is_some() {
+            r#try!(self.write_all(&tokens));
+        }
+        Ok(())
+    }
+}
+
+/// An advisory lock on a token file, held from reading the
+/// tokens until the new file has been renamed into place
+struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    fn acquire(path: &Path) -> TwitchResult<FileLock> {
+        let mut lock = path.as_os_str().to_os_string();
+        lock.push(".lock");
+        let lock = PathBuf::from(lock);
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock)
+            {
+                Ok(_) => return Ok(FileLock { path: lock }),
+                Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    let age = fs::metadata(&lock)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|t| t.elapsed().ok());
+                    if age > Some(STALE_LOCK) {
+                        let _ = fs::remove_file(&lock);
+                    } else {
+                        thread::sleep(Duration::from_millis(5));
+                    }
+                }
+                Err(e) => return Err(ApiError::from(e)),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// A temporary file next to `path` that no other writer,
+/// in this or another process, picks as well
+fn temp_path(path: &Path) -> TwitchResult<PathBuf> {
+    let mut suffix = [0u8; 8];
+    r#try!(SystemRandom::new()
+        .fill(&mut suffix)
+        .map_err(|_| crypto_error("rng_failure", "Failed to generate a file name")));
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(format!(".{}.", process::id()));
+    tmp.push(
+        suffix
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>(),
+    );
+    tmp.push(".tmp");
+    Ok(PathBuf::from(tmp))
+}
+
+#[cfg(unix)]
+fn create_private(path: &Path) -> io::Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn create_private(path: &Path) -> io::Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+}
+
+fn crypto_error(error: &str, description: &str) -> ApiError {
+    ApiError::from(AuthError {
+        error: error.to_owned(),
+        description: description.to_owned(),
+    })
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        &SHA256,
+        PBKDF2_ITERATIONS,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+/// Encrypts `data` as `MAGIC || salt || nonce || ciphertext || tag`
+fn seal(passphrase: &str, data: &[u8]) -> TwitchResult<Vec<u8>> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    r#try!(rng
+        .fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| crypto_error("rng_failure", "Failed to generate a salt")));
+
+    let key = r#try!(
+        SealingKey::new(&CHACHA20_POLY1305, &derive_key(passphrase, &salt))
+            .map_err(|_| crypto_error("crypto_failure", "Failed to create the sealing key"))
+    );
+    let tag_len = CHACHA20_POLY1305.tag_len();
+    let mut in_out = data.to_vec();
+    in_out.resize(data.len() + tag_len, 0);
+    let len = r#try!(seal_in_place(&key, &nonce, MAGIC, &mut in_out, tag_len)
+        .map_err(|_| crypto_error("crypto_failure", "Failed to encrypt the token store")));
+
+    let mut out = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + len);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&in_out[..len]);
+    Ok(out)
+}
+
+fn open(passphrase: &str, data: &[u8]) -> TwitchResult<Vec<u8>> {
+    let header = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if data.len() < header || &data[..MAGIC.len()] != MAGIC {
+        return Err(crypto_error(
+            "invalid_store",
+            "The token store is not an encrypted store",
+        ));
+    }
+    let salt = &data[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce = &data[MAGIC.len() + SALT_LEN..header];
+
+    let key = r#try!(
+        OpeningKey::new(&CHACHA20_POLY1305, &derive_key(passphrase, salt))
+            .map_err(|_| crypto_error("crypto_failure", "Failed to create the opening key"))
+    );
+    let mut in_out = data[header..].to_vec();
+    let plain = r#try!(
+        open_in_place(&key, nonce, MAGIC, 0, &mut in_out).map_err(|_| {
+            crypto_error(
+                "invalid_passphrase",
+                "The token store could not be decrypted with this passphrase",
+            )
+        })
+    );
+    Ok(plain.to_vec())
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+    use std::thread;
+
+    use super::super::super::response::ApiError;
+    use super::super::AccessToken;
+    use super::{FileTokenStore, MemoryTokenStore, TokenKey, TokenStore};
+
+    fn token(access: &str) -> AccessToken {
+        AccessToken {
+            access_token: String::from(access),
+            refresh_token: Some(String::from("refresh")),
+            expires_in: Some(3600),
+            scope: vec![String::from("chat:read")],
+            token_type: String::from("bearer"),
+            id_token: None,
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("twitch_api-{}-{}.json", name, process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn round_trip<S: TokenStore>(store: &S) {
+        let user = TokenKey::User(String::from("1234"));
+        let app = TokenKey::App(String::from("cid"));
+
+        assert_eq!(store.load(&user).unwrap(), None);
+        store.save(&user, &token("user-token")).unwrap();
+        store.save(&app, &token("app-token")).unwrap();
+        assert_eq!(store.load(&user).unwrap(), Some(token("user-token")));
+        assert_eq!(store.load(&app).unwrap(), Some(token("app-token")));
+
+        store.save(&user, &token("new-token")).unwrap();
+        assert_eq!(store.load(&user).unwrap(), Some(token("new-token")));
+
+        store.delete(&user).unwrap();
+        assert_eq!(store.load(&user).unwrap(), None);
+        assert_eq!(store.load(&app).unwrap(), Some(token("app-token")));
+    }
+
+    #[test]
+    fn memory_store() {
+        round_trip(&MemoryTokenStore::new());
+    }
+
+    #[test]
+    fn file_store() {
+        let path = temp_path("plain");
+        round_trip(&FileTokenStore::new(&path));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unique_temp_path() {
+        let path = temp_path("tmp");
+        let a = super::temp_path(&path).unwrap();
+        let b = super::temp_path(&path).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a.parent(), path.parent());
+        assert!(a.to_str().unwrap().ends_with(".tmp"));
+    }
+
+    #[test]
+    fn concurrent_writers() {
+        let path = temp_path("concurrent");
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    // separate stores share no lock, like separate processes
+                    let store = FileTokenStore::new(&path);
+                    for _ in 0..10 {
+                        store
+                            .save(&TokenKey::User(i.to_string()), &token("user-token"))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for w in writers {
+            w.join().unwrap();
+        }
+        let store = FileTokenStore::new(&path);
+        for i in 0..8 {
+            assert_eq!(
+                store.load(&TokenKey::User(i.to_string())).unwrap(),
+                Some(token("user-token"))
+            );
+        }
+        assert!(!path.with_extension("json.lock").exists());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn encrypted_file_store() {
+        let path = temp_path("encrypted");
+        let key = TokenKey::User(String::from("1234"));
+        round_trip(&FileTokenStore::encrypted(&path, "hunter2"));
+
+        let store = FileTokenStore::encrypted(&path, "hunter2");
+        store.save(&key, &token("secret-token")).unwrap();
+        let raw = fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("secret-token"));
+        assert_eq!(
+            FileTokenStore::encrypted(&path, "hunter2")
+                .load(&key)
+                .unwrap(),
+            Some(token("secret-token"))
+        );
+
+        match FileTokenStore::encrypted(&path, "wrong").load(&key) {
+            Err(ApiError::AuthError(e)) => assert_eq!(e.error, "invalid_passphrase"),
+            r => panic!("{:?}", r),
+        }
+        assert!(FileTokenStore::new(&path).load(&key).is_err());
+        fs::remove_file(&path).unwrap();
+
+        let debug = format!("{:?}", store);
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("<redacted>"));
+    }
+}
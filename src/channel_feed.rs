@@ -20,6 +20,7 @@ extern crate serde_json;
 
 use self::chrono::prelude::*;
 
+use super::auth::Scope;
 use super::response::TwitchResult;
 use super::users::User;
 use super::TwitchClient;
@@ -69,6 +70,7 @@ pub fn get_posts<'c>(c: &'c TwitchClient, chan_id: &str) -> TwitchResult<FeedPos
 /// #### Authentication: `channel_feed_edit`
 ///
 pub fn new_post(c: &TwitchClient, chan_id: &str, data: &str) -> TwitchResult<NewFeedPostResponse> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.post::<NewContent, NewFeedPostResponse>(
         &format!("/feed/{}/posts", chan_id),
         &NewContent { content: data },
@@ -81,6 +83,7 @@ pub fn new_post(c: &TwitchClient, chan_id: &str, data: &str) -> TwitchResult<New
 /// #### Authentication: `channel_feed_edit`
 ///
 pub fn delete_post(c: &TwitchClient, chan_id: &str, post_id: &str) -> TwitchResult<FeedPost> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.delete::<FeedPost>(&format!("/feed/{}/posts/{}", chan_id, post_id)));
     Ok(r)
 }
@@ -99,6 +102,7 @@ pub fn new_post_reaction(
     post_id: &str,
     emote_id: &str,
 ) -> TwitchResult<NewReactionResponse> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.post::<Value, NewReactionResponse>(
         &format!(
             "/feed/{}/posts/{}/reactions?emote_id={}",
@@ -123,6 +127,7 @@ pub fn delete_post_reaction(
     post_id: &str,
     emote_id: &str,
 ) -> TwitchResult<DelReactionResponse> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.delete::<DelReactionResponse>(&format!(
         "/feed/{}/posts/{}/reactions?emote_id={}",
         chan_id, post_id, emote_id
@@ -167,6 +172,7 @@ pub fn new_comment(
     post_id: &str,
     data: &str,
 ) -> TwitchResult<FeedPostComment> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.post::<NewContent, FeedPostComment>(
         &format!("/feed/{}/posts/{}/comments", chan_id, post_id),
         &NewContent { content: data }
@@ -184,6 +190,7 @@ pub fn delete_comment(
     post_id: &str,
     comment_id: &str,
 ) -> TwitchResult<FeedPostComment> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.delete::<FeedPostComment>(&format!(
         "/feed/{}/posts/{}/comments/{}",
         chan_id, post_id, comment_id
@@ -206,6 +213,7 @@ pub fn new_comment_reaction(
     post_id: &str,
     comment_id: &str,
 ) -> TwitchResult<NewReactionResponse> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.post::<Value, NewReactionResponse>(
         &format!(
             "/feed/{}/posts/{}/comments/{}/reactions?emote_id=endorse",
@@ -231,6 +239,7 @@ pub fn delete_comment_reaction(
     post_id: &str,
     comment_id: &str,
 ) -> TwitchResult<DelReactionResponse> {
+    r#try!(c.require_scopes(&[Scope::ChannelFeedEdit]));
     let r = r#try!(c.delete::<DelReactionResponse>(&format!(
         "/feed/{}/posts/{}/comments/{}/reactions?emote_id=endorse",
         chan_id, post_id, comment_id
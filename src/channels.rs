@@ -20,6 +20,7 @@ extern crate serde_json;
 
 use self::chrono::prelude::*;
 
+use super::auth::Scope;
 use super::communities::Community;
 use super::response::TwitchResult;
 use super::users::User;
@@ -36,6 +37,7 @@ use std::io::Write;
 /// #### Authentication: `channel_read`
 ///
 pub fn get(c: &TwitchClient) -> TwitchResult<Channel> {
+    r#try!(c.require_scopes(&[Scope::ChannelRead]));
     let r = r#try!(c.get::<Channel>("/channel"));
     Ok(r)
 }
@@ -54,6 +56,7 @@ pub fn get_by_id(c: &TwitchClient, chan_id: &str) -> TwitchResult<Channel> {
 /// #### Authentication: `channel_read`
 ///
 pub fn editors(c: &TwitchClient, chan_id: &str) -> TwitchResult<ChannelEditors> {
+    r#try!(c.require_scopes(&[Scope::ChannelRead]));
     let r = r#try!(c.get::<ChannelEditors>(&format!("/channels/{}/editors", chan_id)));
     Ok(r)
 }
@@ -64,6 +67,7 @@ pub fn editors(c: &TwitchClient, chan_id: &str) -> TwitchResult<ChannelEditors>
 ///
 /// #### Authentication: `None`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::channels::followers`")]
 pub fn followers(c: &TwitchClient, chan_id: &str) -> TwitchResult<ChannelFollowers> {
     let mut followers = ChannelFollowers {
         follows: Vec::new(),
@@ -95,7 +99,11 @@ pub fn teams(c: &TwitchClient, chan_id: &str) -> TwitchResult<ChannelTeams> {
 ///
 /// #### Authentication: `channel_subscriptions`
 ///
+#[deprecated(
+    note = "Kraken has been shut down, use `helix::subscriptions::broadcaster_subscriptions`"
+)]
 pub fn subscribers(c: &TwitchClient, chan_id: &str) -> TwitchResult<ChannelSubscribers> {
+    r#try!(c.require_scopes(&[Scope::ChannelSubscriptions]));
     let mut subs = Vec::new();
     let mut r = r#try!(
         c.get::<ChannelSubscribers>(&format!("/channels/{}/subscriptions?limit=100", chan_id))
@@ -126,11 +134,13 @@ pub fn subscribers(c: &TwitchClient, chan_id: &str) -> TwitchResult<ChannelSubsc
 ///
 /// #### Authentication: `channel_check_subscription`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::subscriptions::check`")]
 pub fn subscription(
     c: &TwitchClient,
     chan_id: &str,
     user_id: &str,
 ) -> TwitchResult<ChannelSubscription> {
+    r#try!(c.require_scopes(&[Scope::ChannelCheckSubscription]));
     let r = r#try!(
         c.get::<ChannelSubscription>(&format!("/channels/{}/subscriptions/{}", chan_id, user_id))
     );
@@ -156,6 +166,7 @@ pub fn videos<'c>(c: &'c TwitchClient, chan_id: &str) -> TwitchResult<VideosIter
 /// #### Authentication: `channel_editor`
 ///
 pub fn community(c: &TwitchClient, chan_id: &str) -> TwitchResult<Community> {
+    r#try!(c.require_scopes(&[Scope::ChannelEditor]));
     let r = r#try!(c.get::<Community>(&format!("/channels/{}/community", chan_id)));
     Ok(r)
 }
@@ -165,6 +176,7 @@ pub fn community(c: &TwitchClient, chan_id: &str) -> TwitchResult<Community> {
 /// #### Authentication: `channel_editor`
 ///
 pub fn set_community(c: &TwitchClient, chan_id: &str, community_id: &str) -> TwitchResult<Channel> {
+    r#try!(c.require_scopes(&[Scope::ChannelEditor]));
     let r = r#try!(c.put::<Value, Channel>(
         &format!("/channels/{}/community/{}", chan_id, community_id),
         &Value::Null
@@ -183,6 +195,7 @@ pub fn update<'a>(
     chan_id: &str,
     data: &'a UpdateSettings,
 ) -> TwitchResult<Channel> {
+    r#try!(c.require_scopes(&[Scope::ChannelEditor]));
     let mut channel: HashMap<String, &str> = HashMap::new();
     if let Some(status) = data.status {
         channel.insert("status".to_owned(), status);
@@ -227,6 +240,7 @@ pub fn commercial(
     chan_id: &str,
     duration: i32,
 ) -> TwitchResult<CommercialResponse> {
+    r#try!(c.require_scopes(&[Scope::ChannelCommercial]));
     let r = r#try!(c.post::<CommercialDuration, CommercialResponse>(
         &format!("/channels/{}/commercial", chan_id),
         &CommercialDuration { duration: duration }
@@ -245,6 +259,7 @@ pub fn commercial(
 /// #### Authentication: `channel_stream`
 ///
 pub fn reset_stream_key(c: &TwitchClient, chan_id: &str) -> TwitchResult<Channel> {
+    r#try!(c.require_scopes(&[Scope::ChannelStream]));
     let r = r#try!(c.delete::<Channel>(&format!("/channels/{}/stream_key", chan_id)));
     Ok(r)
 }
@@ -451,6 +466,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn followers() {
         let c = new(String::from(CLIENTID));
         match super::followers(&c, CHANID) {
@@ -475,6 +491,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn subscribers() {
         let mut c = new(String::from(CLIENTID));
         c.set_oauth_token(TOKEN);
@@ -492,6 +509,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn subscription() {
         let c = new(String::from(CLIENTID));
         match super::subscription(&c, CHANID, CHANID) {
@@ -24,6 +24,7 @@ use super::TwitchClient;
 ///
 /// #### Authentication: `None`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::chat::channel_badges`")]
 pub fn get_badges(c: &TwitchClient, chan_id: &str) -> TwitchResult<BadgeSet> {
     let r = r#try!(c.get::<BadgeSet>(&format!("/chat/{}/badges", chan_id)));
     Ok(r)
@@ -37,6 +38,7 @@ pub fn get_badges(c: &TwitchClient, chan_id: &str) -> TwitchResult<BadgeSet> {
 /// Caution: When not specifying the emotesets parameter,
 /// this endpoint returns a large amount of data.
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::chat::emote_sets`")]
 pub fn get_emote_sets(c: &TwitchClient, sets: &[&str]) -> TwitchResult<EmotesBySet> {
     let r = r#try!(c.get::<EmotesBySet>(&format!(
         "/chat/emoticon_images?emotesets={}",
@@ -52,6 +54,7 @@ pub fn get_emote_sets(c: &TwitchClient, sets: &[&str]) -> TwitchResult<EmotesByS
 /// # Remarks
 /// Caution: This endpoint returns a large amount of data.
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::chat::global_emotes`")]
 pub fn get_emotes(c: &TwitchClient) -> TwitchResult<ChatEmotes> {
     let r = r#try!(c.get::<ChatEmotes>("/chat/emoticons"));
     Ok(r)
@@ -115,6 +118,7 @@ mod tests {
     use super::super::tests::CLIENTID;
 
     #[test]
+    #[allow(deprecated)]
     fn get_badges() {
         let c = new(String::from(CLIENTID));
 
@@ -128,6 +132,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn get_emote_sets() {
         let c = new(String::from(CLIENTID));
 
@@ -141,6 +146,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn get_emotes() {
         let c = new(String::from(CLIENTID));
 
@@ -18,6 +18,7 @@
 extern crate chrono;
 extern crate serde_json;
 
+use super::auth::Scope;
 use super::response::TwitchResult;
 use super::users::User;
 use super::TwitchClient;
@@ -58,6 +59,7 @@ pub fn update<'a>(
     community_id: &str,
     data: &'a UpdateSettings,
 ) -> TwitchResult<Community> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let mut settings: HashMap<String, &str> = HashMap::new();
     if let Some(summary) = data.summary {
         settings.insert("summary".to_owned(), summary);
@@ -82,7 +84,9 @@ pub fn update<'a>(
 ///
 /// #### Authentication: `communities_moderate`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::banned_users`")]
 pub fn bans<'c>(c: &'c TwitchClient, community_id: &str) -> TwitchResult<CommunityBanIterator<'c>> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesModerate]));
     let iter = CommunityBanIterator {
         client: c,
         community_id: String::from(community_id),
@@ -96,7 +100,9 @@ pub fn bans<'c>(c: &'c TwitchClient, community_id: &str) -> TwitchResult<Communi
 ///
 /// #### Authentication: `communities_moderate`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::ban`")]
 pub fn ban(c: &TwitchClient, community_id: &str, user_id: &str) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesModerate]));
     let r = r#try!(c.put::<Value, Value>(
         &format!("/communities/{}/bans/{}", community_id, user_id),
         &Value::Null
@@ -108,7 +114,9 @@ pub fn ban(c: &TwitchClient, community_id: &str, user_id: &str) -> TwitchResult<
 ///
 /// #### Authentication: `communities_moderate`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::unban`")]
 pub fn unban(c: &TwitchClient, community_id: &str, user_id: &str) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesModerate]));
     let r = r#try!(c.delete::<Value>(&format!("/communities/{}/bans/{}", community_id, user_id)));
     Ok(r)
 }
@@ -122,6 +130,7 @@ pub fn set_avatar_image(
     community_id: &str,
     avatar_img: &str,
 ) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let mut data: HashMap<String, &str> = HashMap::new();
     data.insert("avatar_image".to_owned(), avatar_img);
     let r = r#try!(c.post::<HashMap<String, &str>, Value>(
@@ -136,6 +145,7 @@ pub fn set_avatar_image(
 /// #### Authentication: `communities_edit`
 ///
 pub fn delete_avatar_image(c: &TwitchClient, community_id: &str) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let r = r#try!(c.delete::<Value>(&format!("/communities/{}/images/avatar", community_id)));
     Ok(r)
 }
@@ -149,6 +159,7 @@ pub fn set_cover_image(
     community_id: &str,
     cover_img: &str,
 ) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let mut data: HashMap<String, &str> = HashMap::new();
     data.insert("cover_image".to_owned(), cover_img);
     let r = r#try!(c.post::<HashMap<String, &str>, Value>(
@@ -163,6 +174,7 @@ pub fn set_cover_image(
 /// #### Authentication: `communities_edit`
 ///
 pub fn delete_cover_image(c: &TwitchClient, community_id: &str) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let r = r#try!(c.delete::<Value>(&format!("/communities/{}/images/cover", community_id)));
     Ok(r)
 }
@@ -171,7 +183,9 @@ pub fn delete_cover_image(c: &TwitchClient, community_id: &str) -> TwitchResult<
 ///
 /// #### Authentication: `communities_edit`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::moderators`")]
 pub fn moderators(c: &TwitchClient, community_id: &str) -> TwitchResult<Moderators> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let r = r#try!(c.get::<Moderators>(&format!("/communities/{}/moderators", community_id)));
     Ok(r)
 }
@@ -180,7 +194,9 @@ pub fn moderators(c: &TwitchClient, community_id: &str) -> TwitchResult<Moderato
 ///
 /// #### Authentication: `communities_edit`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::add_moderator`")]
 pub fn new_moderator(c: &TwitchClient, community_id: &str, user_id: &str) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let r = r#try!(c.put::<Value, Value>(
         &format!("/communities/{}/moderators/{}", community_id, user_id),
         &Value::Null
@@ -192,11 +208,13 @@ pub fn new_moderator(c: &TwitchClient, community_id: &str, user_id: &str) -> Twi
 ///
 /// #### Authentication: `communities_edit`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::remove_moderator`")]
 pub fn delete_moderator(
     c: &TwitchClient,
     community_id: &str,
     user_id: &str,
 ) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesEdit]));
     let r = r#try!(c.delete::<Value>(&format!(
         "/communities/{}/moderators/{}",
         community_id, user_id
@@ -237,7 +255,9 @@ pub fn report_channel(
 ///
 /// #### Authentication: `communities_moderate`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::banned_users`")]
 pub fn timeouts<'c>(c: &'c TwitchClient, community_id: &str) -> TwitchResult<TimeoutIterator<'c>> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesModerate]));
     let iter = TimeoutIterator {
         client: c,
         community_id: String::from(community_id),
@@ -251,6 +271,7 @@ pub fn timeouts<'c>(c: &'c TwitchClient, community_id: &str) -> TwitchResult<Tim
 ///
 /// #### Authentication: `communities_moderate`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::ban`")]
 pub fn timeout(
     c: &TwitchClient,
     community_id: &str,
@@ -258,6 +279,7 @@ pub fn timeout(
     duration: i32,
     reason: Option<String>,
 ) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesModerate]));
     let mut data: HashMap<String, String> = HashMap::new();
     data.insert("duration".to_owned(), duration.to_string());
     if let Some(reason) = reason {
@@ -274,7 +296,9 @@ pub fn timeout(
 ///
 /// #### Authentication: `communities_moderate`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::moderation::unban`")]
 pub fn delete_timeout(c: &TwitchClient, community_id: &str, user_id: &str) -> TwitchResult<Value> {
+    r#try!(c.require_scopes(&[Scope::CommunitiesModerate]));
     let r = r#try!(c.delete::<Value>(&format!(
         "/communities/{}/timeouts/{}",
         community_id, user_id
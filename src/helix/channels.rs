@@ -0,0 +1,387 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use super::super::auth::Scope;
+use super::super::response::{ApiError, TwitchResult};
+use super::super::TwitchClient;
+use super::{check_len, url, Data, HelixIterator, MAX_IDS};
+
+/// Gets information about up to 100 channels
+///
+/// #### Authentication: `None`
+///
+pub fn get(c: &TwitchClient, broadcaster_ids: &[&str]) -> TwitchResult<Vec<ChannelInformation>> {
+    r#try!(check_len("broadcaster_id", broadcaster_ids, MAX_IDS));
+    let params: Vec<(&str, &str)> = broadcaster_ids
+        .iter()
+        .map(|id| ("broadcaster_id", *id))
+        .collect();
+    let r = r#try!(c.helix_get::<Data<ChannelInformation>>(&url("/channels", &params)));
+    Ok(r.data)
+}
+
+/// Updates the fields of a channel that are set in `data`
+///
+/// `data` is checked with `ModifyChannel::validate` before
+/// the request is sent.
+///
+/// #### Authentication: `channel:manage:broadcast`
+///
+pub fn modify(c: &TwitchClient, broadcaster_id: &str, data: &ModifyChannel) -> TwitchResult<()> {
+    r#try!(data.validate());
+    r#try!(c.require_scopes(&[Scope::ChannelManageBroadcast]));
+    r#try!(c.helix_patch::<ModifyChannel, ()>(
+        &url("/channels", &[("broadcaster_id", broadcaster_id)]),
+        data
+    ));
+    Ok(())
+}
+
+/// Gets the users that follow a broadcaster, newest first
+///
+/// With `user_id`, only that user is returned if they follow
+/// the broadcaster. The iterator's `total` is the number of
+/// followers. Without the scope, or unless the token user
+/// moderates the channel, Twitch only reports the total.
+///
+/// #### Authentication: `moderator:read:followers`
+///
+pub fn followers<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    user_id: Option<&str>,
+) -> TwitchResult<HelixIterator<'c, Follower>> {
+    let mut params = vec![("broadcaster_id", broadcaster_id)];
+    if let Some(user_id) = user_id {
+        params.push(("user_id", user_id));
+    }
+    Ok(HelixIterator::new(c, url("/channels/followers", &params)))
+}
+
+/// Gets the number of users that follow a broadcaster
+///
+/// #### Authentication: `None`
+///
+pub fn follower_total(c: &TwitchClient, broadcaster_id: &str) -> TwitchResult<i64> {
+    let r = r#try!(c.helix_get::<Data<Follower>>(&url(
+        "/channels/followers",
+        &[("broadcaster_id", broadcaster_id), ("first", "1")]
+    )));
+    Ok(r.total.unwrap_or(0))
+}
+
+/// Gets the broadcasters a user follows, newest first
+///
+/// `user_id` has to match the user the OAuth token belongs to.
+/// With `broadcaster_id`, only that broadcaster is returned if
+/// the user follows them.
+///
+/// #### Authentication: `user:read:follows`
+///
+pub fn followed<'c>(
+    c: &'c TwitchClient,
+    user_id: &str,
+    broadcaster_id: Option<&str>,
+) -> TwitchResult<HelixIterator<'c, FollowedChannel>> {
+    r#try!(c.require_scopes(&[Scope::UserReadFollows]));
+    let mut params = vec![("user_id", user_id)];
+    if let Some(broadcaster_id) = broadcaster_id {
+        params.push(("broadcaster_id", broadcaster_id));
+    }
+    Ok(HelixIterator::new(c, url("/channels/followed", &params)))
+}
+
+///////////////////////////////////////
+// ChannelInformation
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChannelInformation {
+    pub broadcaster_id: String,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub broadcaster_language: String,
+    pub game_id: String,
+    pub game_name: String,
+    pub title: String,
+    /// Stream delay in seconds, Twitch reports `0` unless the
+    /// request is made with the broadcaster's token
+    pub delay: u32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub content_classification_labels: Vec<ContentLabel>,
+    pub is_branded_content: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ContentLabel {
+    DebatedSocialIssuesAndPolitics,
+    DrugsIntoxication,
+    Gambling,
+    /// Set by Twitch from the game, cannot be changed
+    MatureGame,
+    ProfanityVulgarity,
+    SexualThemes,
+    ViolentGraphic,
+}
+
+///////////////////////////////////////
+// Follows
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Follower {
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    pub followed_at: DateTime<UTC>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FollowedChannel {
+    pub broadcaster_id: String,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub followed_at: DateTime<UTC>,
+}
+
+///////////////////////////////////////
+// ModifyChannel
+///////////////////////////////////////
+/// The fields to change, `None` leaves a field as it is
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ModifyChannel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `"0"` or an empty string unsets the game
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+    /// An ISO 639-1 two-letter code, or `"other"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcaster_language: Option<String>,
+    /// Stream delay in seconds, partners only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<u32>,
+    /// Replaces all tags, an empty list removes them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_classification_labels: Option<Vec<ContentClassificationLabel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_branded_content: Option<bool>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct ContentClassificationLabel {
+    pub id: ContentLabel,
+    pub is_enabled: bool,
+}
+
+pub const MAX_TITLE_LEN: usize = 140;
+pub const MAX_DELAY: u32 = 900;
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LEN: usize = 25;
+
+impl ModifyChannel {
+    /// Checks the fields against the limits Twitch enforces
+    pub fn validate(&self) -> TwitchResult<()> {
+        if self.title.is_none()
+            && self.game_id.is_none()
+            && self.broadcaster_language.is_none()
+            && self.delay.is_none()
+            && self.tags.is_none()
+            && self.content_classification_labels.is_none()
+            && self.is_branded_content.is_none()
+        {
+            return Err(ApiError::invalid_request("No channel fields to modify"));
+        }
+        if let Some(ref title) = self.title {
+            if title.trim().is_empty() || title.chars().count() > MAX_TITLE_LEN {
+                return Err(ApiError::invalid_request(&format!(
+                    "The title has to be 1 to {} characters long",
+                    MAX_TITLE_LEN
+                )));
+            }
+        }
+        if let Some(ref lang) = self.broadcaster_language {
+            let iso = lang.len() == 2 && lang.chars().all(|c| c.is_ascii_lowercase());
+            if !iso && lang != "other" {
+                return Err(ApiError::invalid_request(&format!(
+                    "Invalid broadcaster language {}",
+                    lang
+                )));
+            }
+        }
+        if let Some(delay) = self.delay {
+            if delay > MAX_DELAY {
+                return Err(ApiError::invalid_request(&format!(
+                    "The delay can be at most {} seconds",
+                    MAX_DELAY
+                )));
+            }
+        }
+        if let Some(ref tags) = self.tags {
+            r#try!(check_len("tags", tags, MAX_TAGS));
+            for tag in tags {
+                let valid = !tag.is_empty()
+                    && tag.chars().count() <= MAX_TAG_LEN
+                    && tag.chars().all(|c| c.is_alphanumeric());
+                if !valid {
+                    return Err(ApiError::invalid_request(&format!(
+                        "Invalid tag {:?}, tags are 1 to {} letters or digits",
+                        tag, MAX_TAG_LEN
+                    )));
+                }
+            }
+        }
+        if let Some(ref labels) = self.content_classification_labels {
+            if labels.iter().any(|l| l.id == ContentLabel::MatureGame) {
+                return Err(ApiError::invalid_request(
+                    "The MatureGame label cannot be set",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::Data;
+    use super::{
+        ChannelInformation, ContentClassificationLabel, ContentLabel, FollowedChannel, Follower,
+        ModifyChannel,
+    };
+
+    #[test]
+    fn channel_information() {
+        let r: Data<ChannelInformation> = serde_json::from_str(
+            r#"{"data":[{
+                "broadcaster_id":"141981764",
+                "broadcaster_login":"twitchdev",
+                "broadcaster_name":"TwitchDev",
+                "broadcaster_language":"en",
+                "game_id":"509670",
+                "game_name":"Science & Technology",
+                "title":"TwitchDev Monthly Update // May 6, 2021",
+                "delay":0,
+                "tags":["DevsInTheKnow"],
+                "content_classification_labels":["Gambling","DrugsIntoxication","MatureGame"],
+                "is_branded_content":false
+            }]}"#,
+        )
+        .unwrap();
+        let channel = &r.data[0];
+        assert_eq!(channel.tags, vec!["DevsInTheKnow"]);
+        assert_eq!(
+            channel.content_classification_labels,
+            vec![
+                ContentLabel::Gambling,
+                ContentLabel::DrugsIntoxication,
+                ContentLabel::MatureGame
+            ]
+        );
+    }
+
+    #[test]
+    fn modify_channel() {
+        let data = ModifyChannel {
+            title: Some(String::from("Hello")),
+            delay: Some(5),
+            content_classification_labels: Some(vec![ContentClassificationLabel {
+                id: ContentLabel::Gambling,
+                is_enabled: true,
+            }]),
+            ..ModifyChannel::default()
+        };
+        assert!(data.validate().is_ok());
+        assert_eq!(
+            serde_json::to_string(&data).unwrap(),
+            r#"{"title":"Hello","delay":5,"content_classification_labels":[{"id":"Gambling","is_enabled":true}]}"#
+        );
+
+        let invalid = vec![
+            ModifyChannel::default(),
+            ModifyChannel {
+                title: Some(String::from("")),
+                ..ModifyChannel::default()
+            },
+            ModifyChannel {
+                title: Some("x".repeat(141)),
+                ..ModifyChannel::default()
+            },
+            ModifyChannel {
+                broadcaster_language: Some(String::from("english")),
+                ..ModifyChannel::default()
+            },
+            ModifyChannel {
+                delay: Some(901),
+                ..ModifyChannel::default()
+            },
+            ModifyChannel {
+                tags: Some(vec![String::from("two words")]),
+                ..ModifyChannel::default()
+            },
+            ModifyChannel {
+                tags: Some(vec![String::from("tag"); 11]),
+                ..ModifyChannel::default()
+            },
+            ModifyChannel {
+                content_classification_labels: Some(vec![ContentClassificationLabel {
+                    id: ContentLabel::MatureGame,
+                    is_enabled: true,
+                }]),
+                ..ModifyChannel::default()
+            },
+        ];
+        for data in invalid {
+            assert!(data.validate().is_err(), "{:?}", data);
+        }
+        assert!(ModifyChannel {
+            broadcaster_language: Some(String::from("other")),
+            tags: Some(vec![]),
+            ..ModifyChannel::default()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn follows() {
+        let r: Data<Follower> = serde_json::from_str(
+            r#"{"total":8,"data":[{"user_id":"11111","user_name":"UserDisplayName","user_login":"userloginname","followed_at":"2022-05-24T22:22:08Z"}],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjp7Ik9mZnNldCI6NX19"}}"#,
+        )
+        .unwrap();
+        assert_eq!(r.total, Some(8));
+        assert_eq!(r.data[0].followed_at.timestamp(), 1653430928);
+
+        let r: Data<FollowedChannel> = serde_json::from_str(
+            r#"{"total":1,"data":[{"broadcaster_id":"654321","broadcaster_login":"basketweaver101","broadcaster_name":"BasketWeaver101","followed_at":"2022-05-24T22:22:08Z"}],"pagination":{}}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].broadcaster_login, "basketweaver101");
+    }
+}
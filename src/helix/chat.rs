@@ -0,0 +1,894 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+use super::super::auth::Scope;
+use super::super::response::{ApiError, TwitchResult};
+use super::super::TwitchClient;
+use super::{check_len, first, token_user_id, url, Data, HelixIterator, Pagination, MAX_IDS};
+
+use std::collections::HashSet;
+use std::fmt;
+
+/// The template Twitch currently returns with every emote list
+pub const EMOTE_URL_TEMPLATE: &'static str =
+    "https://static-cdn.jtvnw.net/emoticons/v2/{{id}}/{{format}}/{{theme_mode}}/{{scale}}";
+
+/// At most this many emote sets can be requested at once
+pub const MAX_EMOTE_SETS: usize = 25;
+
+/// The largest page `Get Chatters` returns
+pub const MAX_CHATTERS_PAGE: usize = 1000;
+
+/// Gets the emotes every user can use in chat
+///
+/// #### Authentication: `None`
+///
+pub fn global_emotes(c: &TwitchClient) -> TwitchResult<Emotes> {
+    let r = r#try!(c.helix_get::<Emotes>("/chat/emotes/global"));
+    Ok(r)
+}
+
+/// Gets the custom emotes of a broadcaster, like subscriber,
+/// Bits tier and follower emotes
+///
+/// #### Authentication: `None`
+///
+pub fn channel_emotes(c: &TwitchClient, broadcaster_id: &str) -> TwitchResult<Emotes> {
+    let r =
+        r#try!(c.helix_get::<Emotes>(&url("/chat/emotes", &[("broadcaster_id", broadcaster_id)])));
+    Ok(r)
+}
+
+/// Gets the emotes in one or more emote sets
+///
+/// Lists longer than 25 sets are split into several requests.
+///
+/// #### Authentication: `None`
+///
+pub fn emote_sets(c: &TwitchClient, emote_set_ids: &[&str]) -> TwitchResult<Emotes> {
+    let params: Vec<(&str, &str)> = emote_set_ids
+        .iter()
+        .map(|id| ("emote_set_id", *id))
+        .collect();
+    let mut emotes = Emotes {
+        data: Vec::new(),
+        template: String::from(EMOTE_URL_TEMPLATE),
+        pagination: Pagination::default(),
+    };
+    for batch in params.chunks(MAX_EMOTE_SETS) {
+        let r = r#try!(c.helix_get::<Emotes>(&url("/chat/emotes/set", batch)));
+        emotes.data.extend(r.data);
+        emotes.template = r.template;
+    }
+    Ok(emotes)
+}
+
+/// Gets all emotes a user can use in chat, across all pages
+///
+/// With `broadcaster_id`, follower emotes of that channel
+/// are included as well.
+///
+/// #### Authentication: `user:read:emotes`
+///
+pub fn user_emotes(
+    c: &TwitchClient,
+    user_id: &str,
+    broadcaster_id: Option<&str>,
+) -> TwitchResult<Emotes> {
+    r#try!(c.require_scopes(&[Scope::UserReadEmotes]));
+    let mut params = vec![("user_id", user_id)];
+    if let Some(broadcaster_id) = broadcaster_id {
+        params.push(("broadcaster_id", broadcaster_id));
+    }
+    let path = url("/chat/emotes/user", &params);
+
+    let mut emotes = r#try!(c.helix_get::<Emotes>(&path));
+    while let Some(cursor) = emotes.pagination.cursor.take().filter(|c| !c.is_empty()) {
+        let r = r#try!(c.helix_get::<Emotes>(&url(&path, &[("after", &cursor)])));
+        emotes.data.extend(r.data);
+        emotes.pagination = r.pagination;
+    }
+    Ok(emotes)
+}
+
+/// Gets the chat badges every user can have
+///
+/// #### Authentication: `None`
+///
+pub fn global_badges(c: &TwitchClient) -> TwitchResult<Vec<BadgeSet>> {
+    let r = r#try!(c.helix_get::<Data<BadgeSet>>("/chat/badges/global"));
+    Ok(r.data)
+}
+
+/// Gets the custom chat badges of a broadcaster, like
+/// subscriber and Bits badges
+///
+/// #### Authentication: `None`
+///
+pub fn channel_badges(c: &TwitchClient, broadcaster_id: &str) -> TwitchResult<Vec<BadgeSet>> {
+    let r = r#try!(
+        c.helix_get::<Data<BadgeSet>>(&url("/chat/badges", &[("broadcaster_id", broadcaster_id)]))
+    );
+    Ok(r.data)
+}
+
+/// Gets the chat settings of a broadcaster's chat room
+///
+/// `non_moderator_chat_delay` is only included when
+/// `moderator_id` is set to a moderator of the channel.
+///
+/// #### Authentication: `None`, `moderator:read:chat_settings` for the chat delay
+///
+pub fn settings(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: Option<&str>,
+) -> TwitchResult<ChatSettings> {
+    let mut params = vec![("broadcaster_id", broadcaster_id)];
+    if let Some(moderator_id) = moderator_id {
+        params.push(("moderator_id", moderator_id));
+    }
+    let r = r#try!(c.helix_get::<Data<ChatSettings>>(&url("/chat/settings", &params)));
+    first(r.data)
+}
+
+/// Updates the chat settings that are set in `data`
+///
+/// `moderator_id` has to match the user the OAuth token
+/// belongs to, pass the broadcaster ID for their own chat.
+///
+/// #### Authentication: `moderator:manage:chat_settings`
+///
+pub fn update_settings(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    data: &UpdateChatSettings,
+) -> TwitchResult<ChatSettings> {
+    r#try!(data.validate());
+    r#try!(c.require_scopes(&[Scope::ModeratorManageChatSettings]));
+    let r = r#try!(c.helix_patch::<UpdateChatSettings, Data<ChatSettings>>(
+        &url(
+            "/chat/settings",
+            &[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", moderator_id)
+            ]
+        ),
+        data
+    ));
+    first(r.data)
+}
+
+/// Sends a highlighted announcement to a broadcaster's chat
+///
+/// #### Authentication: `moderator:manage:announcements`
+///
+pub fn send_announcement(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    announcement: &Announcement,
+) -> TwitchResult<()> {
+    r#try!(check_message(&announcement.message));
+    r#try!(c.require_scopes(&[Scope::ModeratorManageAnnouncements]));
+    r#try!(c.helix_post::<Announcement, ()>(
+        &url(
+            "/chat/announcements",
+            &[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", moderator_id)
+            ]
+        ),
+        announcement
+    ));
+    Ok(())
+}
+
+/// Gives a shoutout to another broadcaster in a live channel
+///
+/// #### Authentication: `moderator:manage:shoutouts`
+///
+pub fn shoutout(
+    c: &TwitchClient,
+    from_broadcaster_id: &str,
+    to_broadcaster_id: &str,
+    moderator_id: &str,
+) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ModeratorManageShoutouts]));
+    r#try!(c.helix_post::<(), ()>(
+        &url(
+            "/chat/shoutouts",
+            &[
+                ("from_broadcaster_id", from_broadcaster_id),
+                ("to_broadcaster_id", to_broadcaster_id),
+                ("moderator_id", moderator_id)
+            ]
+        ),
+        &()
+    ));
+    Ok(())
+}
+
+/// Sends a message to a broadcaster's chat, optionally as a reply
+///
+/// The result tells whether the message was sent or dropped,
+/// for example by AutoMod.
+///
+/// #### Authentication: `user:write:chat`
+///
+pub fn send_message(c: &TwitchClient, message: &ChatMessage) -> TwitchResult<SentMessage> {
+    r#try!(check_message(&message.message));
+    r#try!(c.require_scopes(&[Scope::UserWriteChat]));
+    let r = r#try!(c.helix_post::<ChatMessage, Data<SentMessage>>("/chat/messages", message));
+    first(r.data)
+}
+
+/// Gets the chat name colors of up to 100 users
+///
+/// Users that never set a color have an empty `color`.
+///
+/// #### Authentication: `None`
+///
+pub fn user_colors(c: &TwitchClient, user_ids: &[&str]) -> TwitchResult<Vec<UserChatColor>> {
+    r#try!(check_len("user_id", user_ids, MAX_IDS));
+    let params: Vec<(&str, &str)> = user_ids.iter().map(|id| ("user_id", *id)).collect();
+    let r = r#try!(c.helix_get::<Data<UserChatColor>>(&url("/chat/color", &params)));
+    Ok(r.data)
+}
+
+/// Updates the chat name color of the user the OAuth token belongs to
+///
+/// #### Authentication: `user:manage:chat_color`
+///
+pub fn update_user_color(c: &TwitchClient, user_id: &str, color: &ChatColor) -> TwitchResult<()> {
+    r#try!(color.validate());
+    r#try!(c.require_scopes(&[Scope::UserManageChatColor]));
+    let color = color.to_string();
+    r#try!(c.helix_put::<(), ()>(
+        &url("/chat/color", &[("user_id", user_id), ("color", &color)]),
+        &()
+    ));
+    Ok(())
+}
+
+fn check_message(message: &str) -> TwitchResult<()> {
+    if message.trim().is_empty() || message.chars().count() > MAX_MESSAGE_LEN {
+        return Err(ApiError::invalid_request(&format!(
+            "Chat messages have to be 1 to {} characters long",
+            MAX_MESSAGE_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Gets the users in a broadcaster's chat
+///
+/// The moderator ID is the user of the validated OAuth token,
+/// who has to be the broadcaster or one of their moderators.
+/// The iterator's `total` is the number of users in chat.
+///
+/// #### Authentication: `moderator:read:chatters`
+///
+pub fn chatters<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+) -> TwitchResult<HelixIterator<'c, Chatter>> {
+    r#try!(c.require_scopes(&[Scope::ModeratorReadChatters]));
+    let moderator_id = r#try!(token_user_id(c));
+    Ok(HelixIterator::new(
+        c,
+        url(
+            "/chat/chatters",
+            &[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", &moderator_id),
+            ],
+        ),
+    )
+    .first(MAX_CHATTERS_PAGE))
+}
+
+/// Collects everyone in a broadcaster's chat, see `chatters`
+///
+/// #### Authentication: `moderator:read:chatters`
+///
+pub fn all_chatters(c: &TwitchClient, broadcaster_id: &str) -> TwitchResult<HashSet<Chatter>> {
+    let r = r#try!(r#try!(chatters(c, broadcaster_id)).all());
+    Ok(r.into_iter().collect())
+}
+
+/// Fills in an emote URL template like `EMOTE_URL_TEMPLATE`
+pub fn emote_url(
+    template: &str,
+    id: &str,
+    format: EmoteFormat,
+    scale: EmoteScale,
+    theme_mode: ThemeMode,
+) -> String {
+    template
+        .replace("{{id}}", id)
+        .replace("{{format}}", &format.to_string())
+        .replace("{{scale}}", &scale.to_string())
+        .replace("{{theme_mode}}", &theme_mode.to_string())
+}
+
+///////////////////////////////////////
+// Emotes
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Emotes {
+    pub data: Vec<Emote>,
+    /// See `emote_url`
+    pub template: String,
+    #[serde(default)]
+    pub pagination: Pagination,
+}
+
+impl Emotes {
+    /// The image URL of `emote`, built from the returned template
+    pub fn url(
+        &self,
+        emote: &Emote,
+        format: EmoteFormat,
+        scale: EmoteScale,
+        theme_mode: ThemeMode,
+    ) -> String {
+        emote_url(&self.template, &emote.id, format, scale, theme_mode)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Emote {
+    pub id: String,
+    pub name: String,
+    pub images: EmoteImages,
+    /// The subscriber tier of channel emotes
+    pub tier: Option<String>,
+    /// `subscriptions`, `bitstier`, `follower`, ... for channel,
+    /// set and user emotes
+    pub emote_type: Option<String>,
+    pub emote_set_id: Option<String>,
+    /// The broadcaster of set and user emotes
+    pub owner_id: Option<String>,
+    pub format: Vec<EmoteFormat>,
+    pub scale: Vec<EmoteScale>,
+    pub theme_mode: Vec<ThemeMode>,
+}
+
+impl Emote {
+    /// The image URL, built from `EMOTE_URL_TEMPLATE`
+    ///
+    /// Only the formats, scales and theme modes listed for the
+    /// emote exist.
+    ///
+    pub fn url(&self, format: EmoteFormat, scale: EmoteScale, theme_mode: ThemeMode) -> String {
+        emote_url(EMOTE_URL_TEMPLATE, &self.id, format, scale, theme_mode)
+    }
+}
+
+/// Static image URLs at scales 1.0, 2.0 and 4.0
+#[derive(Deserialize, Debug, Clone)]
+pub struct EmoteImages {
+    pub url_1x: String,
+    pub url_2x: String,
+    pub url_4x: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum EmoteFormat {
+    #[serde(rename = "static")]
+    Static,
+    #[serde(rename = "animated")]
+    Animated,
+}
+
+impl fmt::Display for EmoteFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EmoteFormat::Static => write!(f, "static"),
+            EmoteFormat::Animated => write!(f, "animated"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum EmoteScale {
+    #[serde(rename = "1.0")]
+    Small,
+    #[serde(rename = "2.0")]
+    Medium,
+    #[serde(rename = "3.0")]
+    Large,
+}
+
+impl fmt::Display for EmoteScale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EmoteScale::Small => write!(f, "1.0"),
+            EmoteScale::Medium => write!(f, "2.0"),
+            EmoteScale::Large => write!(f, "3.0"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ThemeMode {
+    #[serde(rename = "light")]
+    Light,
+    #[serde(rename = "dark")]
+    Dark,
+}
+
+impl fmt::Display for ThemeMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ThemeMode::Light => write!(f, "light"),
+            ThemeMode::Dark => write!(f, "dark"),
+        }
+    }
+}
+
+///////////////////////////////////////
+// BadgeSet
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct BadgeSet {
+    pub set_id: String,
+    pub versions: Vec<BadgeVersion>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BadgeVersion {
+    pub id: String,
+    pub image_url_1x: String,
+    pub image_url_2x: String,
+    pub image_url_4x: String,
+    pub title: String,
+    pub description: String,
+    pub click_action: Option<String>,
+    pub click_url: Option<String>,
+}
+
+///////////////////////////////////////
+// ChatSettings
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChatSettings {
+    pub broadcaster_id: String,
+    pub emote_mode: bool,
+    pub follower_mode: bool,
+    /// In minutes
+    pub follower_mode_duration: Option<u32>,
+    pub moderator_id: Option<String>,
+    pub non_moderator_chat_delay: Option<bool>,
+    /// In seconds
+    pub non_moderator_chat_delay_duration: Option<u32>,
+    pub slow_mode: bool,
+    /// In seconds
+    pub slow_mode_wait_time: Option<u32>,
+    pub subscriber_mode: bool,
+    pub unique_chat_mode: bool,
+}
+
+/// The chat settings to change, `None` leaves a setting as it is
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct UpdateChatSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emote_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follower_mode: Option<bool>,
+    /// How long users have to follow before chatting, in minutes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follower_mode_duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_moderator_chat_delay: Option<bool>,
+    /// 2, 4 or 6 seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_moderator_chat_delay_duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slow_mode: Option<bool>,
+    /// 3 to 120 seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slow_mode_wait_time: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscriber_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_chat_mode: Option<bool>,
+}
+
+/// Three months, in minutes
+pub const MAX_FOLLOWER_MODE_DURATION: u32 = 129_600;
+pub const MIN_SLOW_MODE_WAIT_TIME: u32 = 3;
+pub const MAX_SLOW_MODE_WAIT_TIME: u32 = 120;
+pub const MAX_MESSAGE_LEN: usize = 500;
+
+impl UpdateChatSettings {
+    /// Checks the settings against the limits Twitch enforces
+    pub fn validate(&self) -> TwitchResult<()> {
+        if self.emote_mode.is_none()
+            && self.follower_mode.is_none()
+            && self.follower_mode_duration.is_none()
+            && self.non_moderator_chat_delay.is_none()
+            && self.non_moderator_chat_delay_duration.is_none()
+            && self.slow_mode.is_none()
+            && self.slow_mode_wait_time.is_none()
+            && self.subscriber_mode.is_none()
+            && self.unique_chat_mode.is_none()
+        {
+            return Err(ApiError::invalid_request("No chat settings to update"));
+        }
+        if let Some(duration) = self.follower_mode_duration {
+            if duration > MAX_FOLLOWER_MODE_DURATION {
+                return Err(ApiError::invalid_request(&format!(
+                    "The follower mode duration can be at most {} minutes",
+                    MAX_FOLLOWER_MODE_DURATION
+                )));
+            }
+        }
+        if let Some(duration) = self.non_moderator_chat_delay_duration {
+            if duration != 2 && duration != 4 && duration != 6 {
+                return Err(ApiError::invalid_request(
+                    "The chat delay has to be 2, 4 or 6 seconds",
+                ));
+            }
+        }
+        if let Some(wait) = self.slow_mode_wait_time {
+            if wait < MIN_SLOW_MODE_WAIT_TIME || wait > MAX_SLOW_MODE_WAIT_TIME {
+                return Err(ApiError::invalid_request(&format!(
+                    "The slow mode wait time has to be {} to {} seconds",
+                    MIN_SLOW_MODE_WAIT_TIME, MAX_SLOW_MODE_WAIT_TIME
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+///////////////////////////////////////
+// Announcement
+///////////////////////////////////////
+#[derive(Serialize, Debug, Clone)]
+pub struct Announcement {
+    pub message: String,
+    /// Defaults to the channel's accent color
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<AnnouncementColor>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum AnnouncementColor {
+    #[serde(rename = "blue")]
+    Blue,
+    #[serde(rename = "green")]
+    Green,
+    #[serde(rename = "orange")]
+    Orange,
+    #[serde(rename = "purple")]
+    Purple,
+    #[serde(rename = "primary")]
+    Primary,
+}
+
+///////////////////////////////////////
+// ChatMessage
+///////////////////////////////////////
+#[derive(Serialize, Debug, Clone)]
+pub struct ChatMessage {
+    pub broadcaster_id: String,
+    /// Has to match the user the OAuth token belongs to
+    pub sender_id: String,
+    pub message: String,
+    /// The message this one replies to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_parent_message_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SentMessage {
+    pub message_id: String,
+    pub is_sent: bool,
+    pub drop_reason: Option<DropReason>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DropReason {
+    pub code: String,
+    pub message: String,
+}
+
+///////////////////////////////////////
+// ChatColor
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserChatColor {
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    /// A hex color like `#9146FF`, empty if never set
+    pub color: String,
+}
+
+/// A chat name color; `Hex` colors need Turbo or Prime
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatColor {
+    Blue,
+    BlueViolet,
+    CadetBlue,
+    Chocolate,
+    Coral,
+    DodgerBlue,
+    Firebrick,
+    GoldenRod,
+    Green,
+    HotPink,
+    OrangeRed,
+    Red,
+    SeaGreen,
+    SpringGreen,
+    YellowGreen,
+    /// `#RRGGBB`
+    Hex(String),
+}
+
+impl ChatColor {
+    pub fn validate(&self) -> TwitchResult<()> {
+        if let ChatColor::Hex(ref hex) = *self {
+            let valid = hex.len() == 7
+                && hex.starts_with('#')
+                && hex[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                return Err(ApiError::invalid_request(&format!(
+                    "Invalid color {:?}, expected #RRGGBB",
+                    hex
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ChatColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChatColor::Blue => write!(f, "blue"),
+            ChatColor::BlueViolet => write!(f, "blue_violet"),
+            ChatColor::CadetBlue => write!(f, "cadet_blue"),
+            ChatColor::Chocolate => write!(f, "chocolate"),
+            ChatColor::Coral => write!(f, "coral"),
+            ChatColor::DodgerBlue => write!(f, "dodger_blue"),
+            ChatColor::Firebrick => write!(f, "firebrick"),
+            ChatColor::GoldenRod => write!(f, "golden_rod"),
+            ChatColor::Green => write!(f, "green"),
+            ChatColor::HotPink => write!(f, "hot_pink"),
+            ChatColor::OrangeRed => write!(f, "orange_red"),
+            ChatColor::Red => write!(f, "red"),
+            ChatColor::SeaGreen => write!(f, "sea_green"),
+            ChatColor::SpringGreen => write!(f, "spring_green"),
+            ChatColor::YellowGreen => write!(f, "yellow_green"),
+            ChatColor::Hex(ref hex) => write!(f, "{}", hex),
+        }
+    }
+}
+
+///////////////////////////////////////
+// Chatter
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chatter {
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::super::new;
+    use super::super::super::response::ApiError;
+    use super::super::Data;
+    use super::{
+        Announcement, AnnouncementColor, BadgeSet, ChatColor, ChatMessage, ChatSettings, Chatter,
+        EmoteFormat, EmoteScale, Emotes, SentMessage, ThemeMode, UpdateChatSettings,
+    };
+
+    #[test]
+    fn emotes() {
+        let r: Emotes = serde_json::from_str(
+            r#"{"data":[{
+                "id":"304456832",
+                "name":"twitchdevPitchfork",
+                "images":{
+                    "url_1x":"https://static-cdn.jtvnw.net/emoticons/v2/304456832/static/light/1.0",
+                    "url_2x":"https://static-cdn.jtvnw.net/emoticons/v2/304456832/static/light/2.0",
+                    "url_4x":"https://static-cdn.jtvnw.net/emoticons/v2/304456832/static/light/3.0"
+                },
+                "tier":"1000",
+                "emote_type":"subscriptions",
+                "emote_set_id":"301590448",
+                "format":["static","animated"],
+                "scale":["1.0","2.0","3.0"],
+                "theme_mode":["light","dark"]
+            }],"template":"https://static-cdn.jtvnw.net/emoticons/v2/{{id}}/{{format}}/{{theme_mode}}/{{scale}}"}"#,
+        )
+        .unwrap();
+        let emote = &r.data[0];
+        assert_eq!(
+            emote.format,
+            vec![EmoteFormat::Static, EmoteFormat::Animated]
+        );
+        assert_eq!(emote.scale[2], EmoteScale::Large);
+        assert_eq!(emote.theme_mode[1], ThemeMode::Dark);
+        assert_eq!(
+            r.url(
+                emote,
+                EmoteFormat::Animated,
+                EmoteScale::Medium,
+                ThemeMode::Dark
+            ),
+            "https://static-cdn.jtvnw.net/emoticons/v2/304456832/animated/dark/2.0"
+        );
+        assert_eq!(
+            emote.url(EmoteFormat::Static, EmoteScale::Large, ThemeMode::Light),
+            emote.images.url_4x
+        );
+    }
+
+    #[test]
+    fn badges() {
+        let r: Data<BadgeSet> = serde_json::from_str(
+            r#"{"data":[{
+                "set_id":"vip",
+                "versions":[{
+                    "id":"1",
+                    "image_url_1x":"https://static-cdn.jtvnw.net/badges/v1/b817aba4-fad8-49e2-b88a-7cc744dfa6ec/1",
+                    "image_url_2x":"https://static-cdn.jtvnw.net/badges/v1/b817aba4-fad8-49e2-b88a-7cc744dfa6ec/2",
+                    "image_url_4x":"https://static-cdn.jtvnw.net/badges/v1/b817aba4-fad8-49e2-b88a-7cc744dfa6ec/3",
+                    "title":"VIP",
+                    "description":"VIP",
+                    "click_action":"visit_url",
+                    "click_url":"https://help.twitch.tv/customer/en/portal/articles/659115-twitch-chat-badges-guide"
+                }]
+            }]}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].set_id, "vip");
+        assert_eq!(
+            r.data[0].versions[0].click_action.as_deref(),
+            Some("visit_url")
+        );
+    }
+
+    #[test]
+    fn chat_settings() {
+        let r: Data<ChatSettings> = serde_json::from_str(
+            r#"{"data":[{
+                "broadcaster_id":"713936733",
+                "slow_mode":false,
+                "slow_mode_wait_time":null,
+                "follower_mode":true,
+                "follower_mode_duration":0,
+                "subscriber_mode":false,
+                "emote_mode":false,
+                "unique_chat_mode":false,
+                "non_moderator_chat_delay":true,
+                "non_moderator_chat_delay_duration":4
+            }]}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].follower_mode_duration, Some(0));
+        assert_eq!(r.data[0].slow_mode_wait_time, None);
+
+        let update = UpdateChatSettings {
+            slow_mode: Some(true),
+            slow_mode_wait_time: Some(10),
+            ..UpdateChatSettings::default()
+        };
+        assert!(update.validate().is_ok());
+        assert_eq!(
+            serde_json::to_string(&update).unwrap(),
+            r#"{"slow_mode":true,"slow_mode_wait_time":10}"#
+        );
+        for update in vec![
+            UpdateChatSettings::default(),
+            UpdateChatSettings {
+                slow_mode_wait_time: Some(2),
+                ..UpdateChatSettings::default()
+            },
+            UpdateChatSettings {
+                non_moderator_chat_delay_duration: Some(3),
+                ..UpdateChatSettings::default()
+            },
+            UpdateChatSettings {
+                follower_mode_duration: Some(129_601),
+                ..UpdateChatSettings::default()
+            },
+        ] {
+            assert!(update.validate().is_err(), "{:?}", update);
+        }
+    }
+
+    #[test]
+    fn messages() {
+        let announcement = Announcement {
+            message: String::from("Hello chat!"),
+            color: Some(AnnouncementColor::Purple),
+        };
+        assert_eq!(
+            serde_json::to_string(&announcement).unwrap(),
+            r#"{"message":"Hello chat!","color":"purple"}"#
+        );
+        let message = ChatMessage {
+            broadcaster_id: String::from("12826"),
+            sender_id: String::from("141981764"),
+            message: String::from("Hello, world! twitchdevHype"),
+            reply_parent_message_id: Some(String::from("abc-123")),
+        };
+        assert_eq!(
+            serde_json::to_string(&message).unwrap(),
+            r#"{"broadcaster_id":"12826","sender_id":"141981764","message":"Hello, world! twitchdevHype","reply_parent_message_id":"abc-123"}"#
+        );
+
+        let r: Data<SentMessage> = serde_json::from_str(
+            r#"{"data":[{"message_id":"","is_sent":false,"drop_reason":{"code":"msg_duplicate","message":"The message is identical to the one you sent within the last 30 seconds."}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            r.data[0].drop_reason.as_ref().unwrap().code,
+            "msg_duplicate"
+        );
+
+        let c = new(String::from("cid"));
+        let long = ChatMessage {
+            message: "x".repeat(501),
+            ..message
+        };
+        match super::send_message(&c, &long) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn chat_color() {
+        assert_eq!(ChatColor::BlueViolet.to_string(), "blue_violet");
+        assert!(ChatColor::Hex(String::from("#9146FF")).validate().is_ok());
+        assert!(ChatColor::Hex(String::from("9146FF")).validate().is_err());
+        assert!(ChatColor::Hex(String::from("#9146FG")).validate().is_err());
+    }
+
+    #[test]
+    fn chatters() {
+        let r: Data<Chatter> = serde_json::from_str(
+            r#"{"data":[{"user_id":"128393656","user_login":"smittysmithers","user_name":"smittysmithers"}],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjp7Ik9mZnNldCI6NX19"},"total":8}"#,
+        )
+        .unwrap();
+        assert_eq!(r.total, Some(8));
+        assert_eq!(r.data[0].user_login, "smittysmithers");
+
+        let c = new(String::from("cid"));
+        match super::chatters(&c, "12826") {
+            Err(ApiError::AuthError(e)) => assert_eq!(e.error, "unvalidated_token"),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,264 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use super::super::auth::Scope;
+use super::super::response::{ApiError, TwitchResult};
+use super::super::TwitchClient;
+use super::{check_len, url, Data, HelixIterator, MAX_IDS};
+
+use std::cmp;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often `wait_for_clip` asks whether a clip is ready
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Gets clips by ID, or the clips of a broadcaster or game
+///
+/// `filter` only applies to broadcaster and game queries.
+///
+/// #### Authentication: `None`
+///
+pub fn get<'c>(
+    c: &'c TwitchClient,
+    query: &ClipQuery,
+    filter: &ClipFilter,
+) -> TwitchResult<HelixIterator<'c, Clip>> {
+    let mut params: Vec<(&str, &str)> = Vec::new();
+    match *query {
+        ClipQuery::Ids(ref ids) => {
+            r#try!(check_len("id", ids, MAX_IDS));
+            if ids.is_empty() {
+                return Err(ApiError::invalid_request("No clip IDs given"));
+            }
+            params.extend(ids.iter().map(|id| ("id", id.as_str())));
+            return Ok(HelixIterator::new(c, url("/clips", &params)));
+        }
+        ClipQuery::Broadcaster(ref id) => params.push(("broadcaster_id", id)),
+        ClipQuery::Game(ref id) => params.push(("game_id", id)),
+    }
+
+    let started_at = filter.started_at.map(|t| t.to_rfc3339());
+    let ended_at = filter.ended_at.map(|t| t.to_rfc3339());
+    let is_featured = filter.is_featured.map(|f| f.to_string());
+    if let Some(ref started_at) = started_at {
+        params.push(("started_at", started_at));
+    }
+    if let Some(ref ended_at) = ended_at {
+        params.push(("ended_at", ended_at));
+    }
+    if let Some(ref is_featured) = is_featured {
+        params.push(("is_featured", is_featured));
+    }
+    Ok(HelixIterator::new(c, url("/clips", &params)))
+}
+
+/// Creates a clip of a live stream
+///
+/// Twitch processes clips asynchronously, so the clip cannot
+/// be fetched right away; see `wait_for_clip`. With `has_delay`
+/// the clip is taken with the stream delay applied, as
+/// viewers see it.
+///
+/// #### Authentication: `clips:edit`
+///
+pub fn create(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    has_delay: bool,
+) -> TwitchResult<CreatedClip> {
+    r#try!(c.require_scopes(&[Scope::ClipsEdit]));
+    let has_delay = has_delay.to_string();
+    let mut r = r#try!(c.helix_post::<(), Data<CreatedClip>>(
+        &url(
+            "/clips",
+            &[
+                ("broadcaster_id", broadcaster_id),
+                ("has_delay", &has_delay)
+            ]
+        ),
+        &()
+    ));
+    if r.data.is_empty() {
+        return Err(ApiError::empty_response());
+    }
+    Ok(r.data.remove(0))
+}
+
+/// Polls `Get Clips` until the clip `id` is available
+///
+/// Fails with `ApiError::Timeout` if the clip does not show
+/// up within `timeout`, which usually means Twitch failed
+/// to create it.
+///
+/// #### Authentication: `None`
+///
+pub fn wait_for_clip(c: &TwitchClient, id: &str, timeout: Duration) -> TwitchResult<Clip> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let mut r = r#try!(c.helix_get::<Data<Clip>>(&url("/clips", &[("id", id)])));
+        if let Some(clip) = r.data.pop() {
+            return Ok(clip);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(ApiError::timeout());
+        }
+        thread::sleep(cmp::min(POLL_INTERVAL, deadline - now));
+    }
+}
+
+/// Creates a clip and waits for it to become available
+///
+/// #### Authentication: `clips:edit`
+///
+pub fn create_and_wait(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    has_delay: bool,
+    timeout: Duration,
+) -> TwitchResult<Clip> {
+    let created = r#try!(create(c, broadcaster_id, has_delay));
+    wait_for_clip(c, &created.id, timeout)
+}
+
+///////////////////////////////////////
+// ClipQuery
+///////////////////////////////////////
+#[derive(Debug, Clone)]
+pub enum ClipQuery {
+    /// Up to 100 clip IDs
+    Ids(Vec<String>),
+    /// The clips of a broadcaster
+    Broadcaster(String),
+    /// The clips of a game
+    Game(String),
+}
+
+/// Limits the clips of a broadcaster or game
+///
+/// Clips are matched by creation date; without `ended_at`,
+/// the window ends a week after `started_at`.
+///
+#[derive(Debug, Clone, Default)]
+pub struct ClipFilter {
+    pub started_at: Option<DateTime<UTC>>,
+    pub ended_at: Option<DateTime<UTC>>,
+    pub is_featured: Option<bool>,
+}
+
+///////////////////////////////////////
+// Clip
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Clip {
+    pub id: String,
+    pub url: String,
+    pub embed_url: String,
+    pub broadcaster_id: String,
+    pub broadcaster_name: String,
+    pub creator_id: String,
+    pub creator_name: String,
+    /// Empty if the video is unavailable
+    pub video_id: String,
+    pub game_id: String,
+    pub language: String,
+    pub title: String,
+    pub view_count: i64,
+    pub created_at: DateTime<UTC>,
+    pub thumbnail_url: String,
+    /// In seconds
+    pub duration: f64,
+    /// Where the clip starts in the video, in seconds
+    pub vod_offset: Option<i64>,
+    #[serde(default)]
+    pub is_featured: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreatedClip {
+    pub id: String,
+    /// Where the clip can be edited for the next 24 hours
+    pub edit_url: String,
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::super::new;
+    use super::super::super::response::ApiError;
+    use super::super::Data;
+    use super::{Clip, ClipFilter, ClipQuery, CreatedClip};
+
+    #[test]
+    fn clip() {
+        let r: Data<Clip> = serde_json::from_str(
+            r#"{"data":[{
+                "id":"RandomClip1",
+                "url":"https://clips.twitch.tv/AwkwardHelplessSalamanderSwiftRage",
+                "embed_url":"https://clips.twitch.tv/embed?clip=RandomClip1",
+                "broadcaster_id":"1234",
+                "broadcaster_name":"JJ",
+                "creator_id":"123456",
+                "creator_name":"MrMarshall",
+                "video_id":"",
+                "game_id":"33103",
+                "language":"en",
+                "title":"random1",
+                "view_count":10,
+                "created_at":"2017-11-30T22:34:18Z",
+                "thumbnail_url":"https://clips-media-assets.twitch.tv/157589949-preview-480x272.jpg",
+                "duration":12.9,
+                "vod_offset":1957,
+                "is_featured":true
+            }],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjoiIn0"}}"#,
+        )
+        .unwrap();
+        let clip = &r.data[0];
+        assert_eq!(clip.vod_offset, Some(1957));
+        assert!(clip.is_featured);
+
+        let r: Data<CreatedClip> = serde_json::from_str(
+            r#"{"data":[{"id":"FiveWordsForClipSlug","edit_url":"https://clips.twitch.tv/FiveWordsForClipSlug/edit"}]}"#,
+        )
+        .unwrap();
+        assert!(r.data[0].edit_url.ends_with("/edit"));
+    }
+
+    #[test]
+    fn limits() {
+        let c = new(String::from("cid"));
+        match super::get(&c, &ClipQuery::Ids(vec![]), &ClipFilter::default()) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+        let ids = ClipQuery::Ids(vec![String::from("a"); 101]);
+        match super::get(&c, &ids, &ClipFilter::default()) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+use super::super::response::TwitchResult;
+use super::super::TwitchClient;
+use super::{empty_as_none, image_url, url, Data, HelixIterator, MAX_IDS};
+
+/// Gets games sorted by number of current viewers on Twitch, most popular first
+///
+/// #### Authentication: `None`
+///
+pub fn top<'c>(c: &'c TwitchClient) -> TwitchResult<HelixIterator<'c, Game>> {
+    Ok(HelixIterator::new(c, String::from("/games/top")))
+}
+
+/// Gets games by ID, exact name or IGDB ID
+///
+/// Lists longer than 100 entries in total are split into
+/// several requests. Games that do not exist are left out
+/// of the result.
+///
+/// #### Authentication: `None`
+///
+pub fn get(
+    c: &TwitchClient,
+    ids: &[&str],
+    names: &[&str],
+    igdb_ids: &[&str],
+) -> TwitchResult<Vec<Game>> {
+    let params: Vec<(&str, &str)> = ids
+        .iter()
+        .map(|id| ("id", *id))
+        .chain(names.iter().map(|name| ("name", *name)))
+        .chain(igdb_ids.iter().map(|id| ("igdb_id", *id)))
+        .collect();
+    let mut games = Vec::with_capacity(params.len());
+    for batch in params.chunks(MAX_IDS) {
+        let r = r#try!(c.helix_get::<Data<Game>>(&url("/games", batch)));
+        games.extend(r.data);
+    }
+    Ok(games)
+}
+
+///////////////////////////////////////
+// Game
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Game {
+    pub id: String,
+    pub name: String,
+    /// Contains `{width}` and `{height}` placeholders,
+    /// see `box_art`
+    pub box_art_url: String,
+    /// `None` if the game is not on IGDB
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub igdb_id: Option<String>,
+}
+
+impl Game {
+    /// The box art URL for the given size
+    pub fn box_art(&self, width: u32, height: u32) -> String {
+        image_url(&self.box_art_url, width, height)
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::Data;
+    use super::Game;
+
+    #[test]
+    fn game() {
+        let r: Data<Game> = serde_json::from_str(
+            r#"{"data":[{
+                "id":"33214",
+                "name":"Fortnite",
+                "box_art_url":"https://static-cdn.jtvnw.net/ttv-boxart/33214-{width}x{height}.jpg",
+                "igdb_id":"1905"
+            }],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjp7IkN1cnNvciI6ImV5SnpJam8yTWpjek5qWTBORFUwTUN3aWFTSTZNU3dpWkNJNlptRnNjMlY5In19"}}"#,
+        )
+        .unwrap();
+        let game = &r.data[0];
+        assert_eq!(game.igdb_id.as_deref(), Some("1905"));
+        assert_eq!(
+            game.box_art(52, 72),
+            "https://static-cdn.jtvnw.net/ttv-boxart/33214-52x72.jpg"
+        );
+
+        let r: Data<Game> = serde_json::from_str(
+            r#"{"data":[{"id":"509658","name":"Just Chatting","box_art_url":"","igdb_id":""}]}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].igdb_id, None);
+    }
+}
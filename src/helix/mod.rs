@@ -0,0 +1,329 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+//! Endpoints of the Helix API
+//!
+//! The Kraken modules at the crate root keep working against
+//! the v5 API; the modules below talk to
+//! `https://api.twitch.tv/helix` through the `helix_*` methods
+//! of `TwitchClient`.
+
+extern crate chrono;
+extern crate urlparse;
+
+pub mod channels;
+pub mod chat;
+pub mod clips;
+pub mod games;
+pub mod moderation;
+pub mod polls;
+pub mod search;
+pub mod streams;
+pub mod subscriptions;
+pub mod users;
+pub mod videos;
+
+use self::chrono::prelude::*;
+use self::urlparse::quote_plus;
+
+use serde::de::{self, Deserialize, Deserializer};
+
+use super::response::{ApiError, AuthError, TwitchResult};
+use super::TwitchClient;
+
+use std;
+use std::collections::VecDeque;
+use std::io::Write;
+
+/// Most ID and login filters accept at most this many values
+pub const MAX_IDS: usize = 100;
+
+/// Builds a Helix path with a URL-encoded query string
+///
+/// Repeating a key, as in `&[("id", "1"), ("id", "2")]`, is how
+/// Helix takes lists of values.
+///
+pub fn url(path: &str, params: &[(&str, &str)]) -> String {
+    let mut url = String::from(path);
+    for &(k, v) in params {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(k);
+        url.push('=');
+        url.push_str(&quote_plus(v, b"").unwrap());
+    }
+    url
+}
+
+/// Fills in the `{width}` and `{height}` placeholders of a
+/// Helix image URL template
+///
+/// Video thumbnails spell them `%{width}` and `%{height}`,
+/// which works too.
+///
+pub fn image_url(template: &str, width: u32, height: u32) -> String {
+    let (width, height) = (width.to_string(), height.to_string());
+    template
+        .replace("%{width}", &width)
+        .replace("%{height}", &height)
+        .replace("{width}", &width)
+        .replace("{height}", &height)
+}
+
+/// Fails with `ApiError::InvalidRequest` if more than `max`
+/// values are passed for the parameter `name`
+pub fn check_len<T>(name: &str, values: &[T], max: usize) -> TwitchResult<()> {
+    if values.len() > max {
+        return Err(ApiError::invalid_request(&format!(
+            "At most {} values are allowed for {}, got {}",
+            max,
+            name,
+            values.len()
+        )));
+    }
+    Ok(())
+}
+
+/// The user ID of the validated OAuth token, used where
+/// Helix wants the acting moderator or user
+///
+/// Fails with an `unvalidated_token` `AuthError` unless
+/// `TwitchClient::validate_token` has been called with a
+/// user access token.
+///
+pub fn token_user_id(c: &TwitchClient) -> TwitchResult<String> {
+    match c.token_info().and_then(|t| t.user_id.clone()) {
+        Some(id) => Ok(id),
+        None => Err(ApiError::from(AuthError {
+            error: "unvalidated_token".to_owned(),
+            description: "Validate a user access token first".to_owned(),
+        })),
+    }
+}
+
+/// The single item of a response, as returned by most
+/// endpoints that create or update something
+pub fn first<T>(mut data: Vec<T>) -> TwitchResult<T> {
+    if data.is_empty() {
+        return Err(ApiError::empty_response());
+    }
+    Ok(data.remove(0))
+}
+
+/// Deserializes the empty strings Helix uses for unset
+/// values as `None`, use with `#[serde(default)]`
+pub fn empty_as_none<D: Deserializer>(d: D) -> Result<Option<String>, D::Error> {
+    let s = r#try!(Option::<String>::deserialize(d));
+    Ok(s.filter(|s| !s.is_empty()))
+}
+
+/// Deserializes the empty strings Helix uses for unset
+/// timestamps as `None`, use with `#[serde(default)]`
+pub fn empty_as_none_time<D: Deserializer>(d: D) -> Result<Option<DateTime<UTC>>, D::Error> {
+    match r#try!(Option::<String>::deserialize(d)) {
+        Some(ref s) if !s.is_empty() => s
+            .parse::<DateTime<UTC>>()
+            .map(Some)
+            .map_err(|e| de::Error::custom(e.to_string())),
+        _ => Ok(None),
+    }
+}
+
+///////////////////////////////////////
+// Data
+///////////////////////////////////////
+/// The envelope every Helix response comes in
+#[derive(Deserialize, Debug)]
+pub struct Data<T> {
+    pub data: Vec<T>,
+    #[serde(default)]
+    pub pagination: Pagination,
+    pub total: Option<i64>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Pagination {
+    pub cursor: Option<String>,
+}
+
+///////////////////////////////////////
+// HelixIterator
+///////////////////////////////////////
+/// Walks a paginated Helix endpoint page by page
+///
+/// Iterating prints errors to stderr and stops, like the Kraken
+/// iterators do; use `next_page` or `all` to handle them.
+///
+#[derive(Debug)]
+pub struct HelixIterator<'c, T> {
+    client: &'c TwitchClient,
+    path: String,
+    cur: VecDeque<T>,
+    cursor: Option<String>,
+    backward: bool,
+    first: Option<usize>,
+    total: Option<i64>,
+    done: bool,
+}
+
+impl<'c, T: Deserialize> HelixIterator<'c, T> {
+    /// `path` may already carry query parameters
+    pub fn new(c: &'c TwitchClient, path: String) -> HelixIterator<'c, T> {
+        HelixIterator {
+            client: c,
+            path: path,
+            cur: VecDeque::new(),
+            cursor: None,
+            backward: false,
+            first: None,
+            total: None,
+            done: false,
+        }
+    }
+
+    /// Sets the number of items requested per page
+    pub fn first(mut self, first: usize) -> HelixIterator<'c, T> {
+        self.first = Some(first);
+        self
+    }
+
+    /// Starts after `cursor`, walking forward
+    pub fn after(mut self, cursor: &str) -> HelixIterator<'c, T> {
+        self.cursor = Some(String::from(cursor));
+        self.backward = false;
+        self
+    }
+
+    /// Starts before `cursor`, walking backward
+    pub fn before(mut self, cursor: &str) -> HelixIterator<'c, T> {
+        self.cursor = Some(String::from(cursor));
+        self.backward = true;
+        self
+    }
+
+    /// The cursor of the next page, to resume paging later
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// The total Twitch reports for endpoints that have one,
+    /// known once the first page has been fetched
+    pub fn total(&self) -> Option<i64> {
+        self.total
+    }
+
+    /// Fetches the next page, `None` once there are no more
+    ///
+    /// Items of a page already buffered by the iterator are
+    /// skipped.
+    ///
+    pub fn next_page(&mut self) -> TwitchResult<Option<Vec<T>>> {
+        self.cur.clear();
+        if self.done {
+            return Ok(None);
+        }
+        let mut params = Vec::new();
+        let first = self.first.map(|f| f.to_string());
+        if let Some(ref first) = first {
+            params.push(("first", first.as_str()));
+        }
+        if let Some(ref cursor) = self.cursor {
+            params.push((
+                if self.backward { "before" } else { "after" },
+                cursor.as_str(),
+            ));
+        }
+        let r = r#try!(self.client.helix_get::<Data<T>>(&url(&self.path, &params)));
+
+        self.total = r.total.or(self.total);
+        self.cursor = r.pagination.cursor.filter(|c| !c.is_empty());
+        if self.cursor.is_none() || r.data.is_empty() {
+            self.done = true;
+        }
+        if r.data.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(r.data))
+    }
+
+    /// Collects every remaining item, failing on the first error
+    pub fn all(mut self) -> TwitchResult<Vec<T>> {
+        let mut all: Vec<T> = self.cur.drain(..).collect();
+        while let Some(page) = r#try!(self.next_page()) {
+            all.extend(page);
+        }
+        Ok(all)
+    }
+}
+
+impl<'c, T: Deserialize> Iterator for HelixIterator<'c, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.cur.is_empty() {
+            match self.next_page() {
+                Ok(Some(page)) => self.cur.extend(page),
+                Ok(None) => (),
+                Err(r) => {
+                    writeln!(&mut std::io::stderr(), "HelixIterator Error: {}", r).unwrap();
+                    self.done = true;
+                }
+            }
+        }
+        self.cur.pop_front()
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::Data;
+
+    #[test]
+    fn url() {
+        assert_eq!(super::url("/users", &[]), "/users");
+        assert_eq!(
+            super::url("/users", &[("id", "1"), ("login", "a b&c")]),
+            "/users?id=1&login=a+b%26c"
+        );
+        assert_eq!(
+            super::url("/streams?type=live", &[("after", "eyJ=")]),
+            "/streams?type=live&after=eyJ%3D"
+        );
+    }
+
+    #[test]
+    fn data() {
+        let d: Data<i32> =
+            serde_json::from_str(r#"{"data":[1,2],"pagination":{"cursor":"abc"},"total":7}"#)
+                .unwrap();
+        assert_eq!(d.data, vec![1, 2]);
+        assert_eq!(d.pagination.cursor.unwrap(), "abc");
+        assert_eq!(d.total, Some(7));
+
+        let d: Data<i32> = serde_json::from_str(r#"{"data":[],"pagination":{}}"#).unwrap();
+        assert!(d.data.is_empty());
+        assert!(d.pagination.cursor.is_none());
+
+        let d: Data<i32> = serde_json::from_str(r#"{"data":[3]}"#).unwrap();
+        assert_eq!(d.data, vec![3]);
+    }
+}
@@ -0,0 +1,1461 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::super::auth::Scope;
+use super::super::response::{ApiError, TwitchResult};
+use super::super::TwitchClient;
+use super::{
+    check_len, empty_as_none, empty_as_none_time, first, url, Data, HelixIterator, MAX_IDS,
+};
+
+/// The longest timeout Twitch accepts, two weeks in seconds
+pub const MAX_TIMEOUT: u32 = 1_209_600;
+
+/// The longest reason a ban or warning may have
+pub const MAX_REASON_LEN: usize = 500;
+
+/// AutoMod levels go from 0 (off) to this
+pub const MAX_AUTOMOD_LEVEL: u8 = 4;
+
+pub const MIN_BLOCKED_TERM_LEN: usize = 2;
+pub const MAX_BLOCKED_TERM_LEN: usize = 500;
+
+/// Bans a user from a broadcaster's chat, or times them out
+/// if the ban has a duration
+///
+/// `moderator_id` has to match the user the OAuth token
+/// belongs to. Banning a user that is already banned fails.
+///
+/// #### Authentication: `moderator:manage:banned_users`
+///
+pub fn ban(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    ban: &Ban,
+) -> TwitchResult<BannedUser> {
+    r#try!(ban.validate());
+    r#try!(c.require_scopes(&[Scope::ModeratorManageBannedUsers]));
+    let r = r#try!(c.helix_post::<BanRequest, Data<BannedUser>>(
+        &url(
+            "/moderation/bans",
+            &[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", moderator_id)
+            ]
+        ),
+        &BanRequest { data: ban }
+    ));
+    first(r.data)
+}
+
+/// Applies a list of bans one after the other
+///
+/// Requests wait for the client's rate limit budget, a ban that
+/// still hits the limit is retried once the window Twitch
+/// reported with it has reset. Bans that fail, for
+/// example because the user is already banned, are reported in
+/// `BulkBan::failed` instead of stopping the others.
+///
+/// #### Authentication: `moderator:manage:banned_users`
+///
+pub fn ban_all(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    bans: &[Ban],
+) -> TwitchResult<BulkBan> {
+    r#try!(c.require_scopes(&[Scope::ModeratorManageBannedUsers]));
+    Ok(ban_each(
+        bans,
+        || until_reset(c),
+        |b| ban(c, broadcaster_id, moderator_id, b),
+    ))
+}
+
+fn ban_each<W, F>(bans: &[Ban], window: W, f: F) -> BulkBan
+where
+    W: Fn() -> Duration,
+    F: Fn(&Ban) -> TwitchResult<BannedUser>,
+{
+    let mut result = BulkBan {
+        banned: Vec::new(),
+        failed: Vec::new(),
+    };
+    for b in bans {
+        match retry_rate_limited(&window, || f(b)) {
+            Ok(banned) => result.banned.push(banned),
+            Err(e) => result.failed.push((b.user_id.clone(), e)),
+        }
+    }
+    result
+}
+
+/// The time until the rate limit window of the client's token
+/// resets, as reported with its last response
+fn until_reset(c: &TwitchClient) -> Duration {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match c.rate_limit() {
+        Some(l) => Duration::from_secs(l.reset.saturating_sub(now)),
+        None => Duration::from_secs(0),
+    }
+}
+
+/// Removes a ban or timeout from a user
+///
+/// `moderator_id` has to match the user the OAuth token
+/// belongs to.
+///
+/// #### Authentication: `moderator:manage:banned_users`
+///
+pub fn unban(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    user_id: &str,
+) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ModeratorManageBannedUsers]));
+    r#try!(c.helix_delete::<()>(&url(
+        "/moderation/bans",
+        &[
+            ("broadcaster_id", broadcaster_id),
+            ("moderator_id", moderator_id),
+            ("user_id", user_id)
+        ]
+    )));
+    Ok(())
+}
+
+/// Gets the users that are banned or timed out in a broadcaster's
+/// chat, optionally only those of up to 100 `user_ids`
+///
+/// #### Authentication: `moderation:read` or `moderator:manage:banned_users`
+///
+pub fn banned_users<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    user_ids: &[&str],
+) -> TwitchResult<HelixIterator<'c, BannedUserInfo>> {
+    r#try!(check_len("user_id", user_ids, MAX_IDS));
+    let mut params = vec![("broadcaster_id", broadcaster_id)];
+    params.extend(user_ids.iter().map(|id| ("user_id", *id)));
+    Ok(HelixIterator::new(c, url("/moderation/banned", &params)))
+}
+
+/// Warns a user in a broadcaster's chat, who has to acknowledge
+/// the warning before chatting again
+///
+/// `moderator_id` has to match the user the OAuth token
+/// belongs to.
+///
+/// #### Authentication: `moderator:manage:warnings`
+///
+pub fn warn(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    user_id: &str,
+    reason: &str,
+) -> TwitchResult<Warning> {
+    if reason.trim().is_empty() {
+        return Err(ApiError::invalid_request("A warning needs a reason"));
+    }
+    r#try!(check_reason(reason));
+    r#try!(c.require_scopes(&[Scope::ModeratorManageWarnings]));
+    let r = r#try!(c.helix_post::<WarnRequest, Data<Warning>>(
+        &url(
+            "/moderation/warnings",
+            &[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", moderator_id)
+            ]
+        ),
+        &WarnRequest {
+            data: WarnData {
+                user_id: user_id,
+                reason: reason,
+            },
+        }
+    ));
+    first(r.data)
+}
+
+/// Checks whether AutoMod would hold messages in a broadcaster's
+/// chat
+///
+/// Lists longer than 100 messages are split into several requests.
+///
+/// #### Authentication: `moderation:read`
+///
+pub fn check_automod(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    messages: &[AutoModCheck],
+) -> TwitchResult<Vec<AutoModStatus>> {
+    r#try!(c.require_scopes(&[Scope::ModerationRead]));
+    let path = url(
+        "/moderation/enforcements/status",
+        &[("broadcaster_id", broadcaster_id)],
+    );
+    let mut lst = Vec::new();
+    for chunk in messages.chunks(MAX_IDS) {
+        let mut r = r#try!(c.helix_post::<AutoModCheckRequest, Data<AutoModStatus>>(
+            &path,
+            &AutoModCheckRequest { data: chunk }
+        ));
+        lst.append(&mut r.data);
+    }
+    Ok(lst)
+}
+
+/// Allows or denies a message AutoMod is holding for review
+///
+/// `user_id` is the moderator and has to match the user the
+/// OAuth token belongs to.
+///
+/// #### Authentication: `moderator:manage:automod`
+///
+pub fn manage_held_message(
+    c: &TwitchClient,
+    user_id: &str,
+    msg_id: &str,
+    action: HeldMessageAction,
+) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ModeratorManageAutomod]));
+    r#try!(c.helix_post::<HeldMessage, ()>(
+        "/moderation/automod/message",
+        &HeldMessage {
+            user_id: user_id,
+            msg_id: msg_id,
+            action: action,
+        }
+    ));
+    Ok(())
+}
+
+/// Gets the AutoMod settings of a broadcaster
+///
+/// #### Authentication: `moderator:read:automod_settings`
+///
+pub fn automod_settings(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+) -> TwitchResult<AutoModSettings> {
+    r#try!(c.require_scopes(&[Scope::ModeratorReadAutomodSettings]));
+    let r = r#try!(
+        c.helix_get::<Data<AutoModSettings>>(&automod_settings_url(broadcaster_id, moderator_id))
+    );
+    first(r.data)
+}
+
+/// Sets every AutoMod category to the levels Twitch derives from
+/// one overall level from 0 (off) to 4 (most filtering)
+///
+/// #### Authentication: `moderator:manage:automod_settings`
+///
+pub fn update_automod_level(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    overall_level: u8,
+) -> TwitchResult<AutoModSettings> {
+    r#try!(check_level("overall_level", overall_level));
+    r#try!(c.require_scopes(&[Scope::ModeratorManageAutomodSettings]));
+    let r = r#try!(c.helix_put::<OverallLevel, Data<AutoModSettings>>(
+        &automod_settings_url(broadcaster_id, moderator_id),
+        &OverallLevel {
+            overall_level: overall_level,
+        }
+    ));
+    first(r.data)
+}
+
+/// Sets the level of each AutoMod category
+///
+/// #### Authentication: `moderator:manage:automod_settings`
+///
+pub fn update_automod_settings(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    levels: &AutoModLevels,
+) -> TwitchResult<AutoModSettings> {
+    r#try!(levels.validate());
+    r#try!(c.require_scopes(&[Scope::ModeratorManageAutomodSettings]));
+    let r = r#try!(c.helix_put::<AutoModLevels, Data<AutoModSettings>>(
+        &automod_settings_url(broadcaster_id, moderator_id),
+        levels
+    ));
+    first(r.data)
+}
+
+fn automod_settings_url(broadcaster_id: &str, moderator_id: &str) -> String {
+    url(
+        "/moderation/automod/settings",
+        &[
+            ("broadcaster_id", broadcaster_id),
+            ("moderator_id", moderator_id),
+        ],
+    )
+}
+
+fn check_level(name: &str, level: u8) -> TwitchResult<()> {
+    if level > MAX_AUTOMOD_LEVEL {
+        return Err(ApiError::invalid_request(&format!(
+            "The AutoMod level {} has to be 0 to {}, got {}",
+            name, MAX_AUTOMOD_LEVEL, level
+        )));
+    }
+    Ok(())
+}
+
+/// Gets the terms a broadcaster blocked in chat
+///
+/// #### Authentication: `moderator:read:blocked_terms`
+///
+pub fn blocked_terms<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+) -> TwitchResult<HelixIterator<'c, BlockedTerm>> {
+    r#try!(c.require_scopes(&[Scope::ModeratorReadBlockedTerms]));
+    Ok(HelixIterator::new(
+        c,
+        blocked_terms_url(broadcaster_id, moderator_id),
+    ))
+}
+
+/// Blocks a word or phrase in a broadcaster's chat
+///
+/// Terms are 2 to 500 characters long and may use `*` as a
+/// wildcard. Adding a term that is already blocked returns
+/// the existing one.
+///
+/// #### Authentication: `moderator:manage:blocked_terms`
+///
+pub fn add_blocked_term(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    text: &str,
+) -> TwitchResult<BlockedTerm> {
+    let len = text.chars().count();
+    if len < MIN_BLOCKED_TERM_LEN || len > MAX_BLOCKED_TERM_LEN {
+        return Err(ApiError::invalid_request(&format!(
+            "Blocked terms have to be {} to {} characters long",
+            MIN_BLOCKED_TERM_LEN, MAX_BLOCKED_TERM_LEN
+        )));
+    }
+    r#try!(c.require_scopes(&[Scope::ModeratorManageBlockedTerms]));
+    let r = r#try!(c.helix_post::<NewBlockedTerm, Data<BlockedTerm>>(
+        &blocked_terms_url(broadcaster_id, moderator_id),
+        &NewBlockedTerm { text: text }
+    ));
+    first(r.data)
+}
+
+/// Unblocks a term by its ID
+///
+/// #### Authentication: `moderator:manage:blocked_terms`
+///
+pub fn remove_blocked_term(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    id: &str,
+) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ModeratorManageBlockedTerms]));
+    r#try!(c.helix_delete::<()>(&url(
+        &blocked_terms_url(broadcaster_id, moderator_id),
+        &[("id", id)]
+    )));
+    Ok(())
+}
+
+fn blocked_terms_url(broadcaster_id: &str, moderator_id: &str) -> String {
+    url(
+        "/moderation/blocked_terms",
+        &[
+            ("broadcaster_id", broadcaster_id),
+            ("moderator_id", moderator_id),
+        ],
+    )
+}
+
+/// Gets the moderators of a broadcaster's channel, optionally
+/// only those of up to 100 `user_ids`
+///
+/// #### Authentication: `moderation:read` or `channel:manage:moderators`
+///
+pub fn moderators<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    user_ids: &[&str],
+) -> TwitchResult<HelixIterator<'c, RosterUser>> {
+    r#try!(check_len("user_id", user_ids, MAX_IDS));
+    let mut params = vec![("broadcaster_id", broadcaster_id)];
+    params.extend(user_ids.iter().map(|id| ("user_id", *id)));
+    Ok(HelixIterator::new(
+        c,
+        url("/moderation/moderators", &params),
+    ))
+}
+
+/// Makes a user a moderator of the broadcaster's channel
+///
+/// VIPs have to be removed as VIP first.
+///
+/// #### Authentication: `channel:manage:moderators`
+///
+pub fn add_moderator(c: &TwitchClient, broadcaster_id: &str, user_id: &str) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ChannelManageModerators]));
+    r#try!(c.helix_post::<(), ()>(
+        &url(
+            "/moderation/moderators",
+            &[("broadcaster_id", broadcaster_id), ("user_id", user_id)]
+        ),
+        &()
+    ));
+    Ok(())
+}
+
+/// Removes a user as moderator of the broadcaster's channel
+///
+/// #### Authentication: `channel:manage:moderators`
+///
+pub fn remove_moderator(c: &TwitchClient, broadcaster_id: &str, user_id: &str) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ChannelManageModerators]));
+    r#try!(c.helix_delete::<()>(&url(
+        "/moderation/moderators",
+        &[("broadcaster_id", broadcaster_id), ("user_id", user_id)]
+    )));
+    Ok(())
+}
+
+/// Gets the VIPs of a broadcaster's channel, optionally only
+/// those of up to 100 `user_ids`
+///
+/// #### Authentication: `channel:read:vips` or `channel:manage:vips`
+///
+pub fn vips<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    user_ids: &[&str],
+) -> TwitchResult<HelixIterator<'c, RosterUser>> {
+    r#try!(check_len("user_id", user_ids, MAX_IDS));
+    let mut params = vec![("broadcaster_id", broadcaster_id)];
+    params.extend(user_ids.iter().map(|id| ("user_id", *id)));
+    Ok(HelixIterator::new(c, url("/channels/vips", &params)))
+}
+
+/// Makes a user a VIP of the broadcaster's channel
+///
+/// Moderators have to be removed as moderator first.
+///
+/// #### Authentication: `channel:manage:vips`
+///
+pub fn add_vip(c: &TwitchClient, broadcaster_id: &str, user_id: &str) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ChannelManageVips]));
+    r#try!(c.helix_post::<(), ()>(
+        &url(
+            "/channels/vips",
+            &[("broadcaster_id", broadcaster_id), ("user_id", user_id)]
+        ),
+        &()
+    ));
+    Ok(())
+}
+
+/// Removes a user as VIP of the broadcaster's channel
+///
+/// #### Authentication: `channel:manage:vips`
+///
+pub fn remove_vip(c: &TwitchClient, broadcaster_id: &str, user_id: &str) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::ChannelManageVips]));
+    r#try!(c.helix_delete::<()>(&url(
+        "/channels/vips",
+        &[("broadcaster_id", broadcaster_id), ("user_id", user_id)]
+    )));
+    Ok(())
+}
+
+/// Gets the channels a user moderates
+///
+/// `user_id` has to match the user the OAuth token belongs to.
+///
+/// #### Authentication: `user:read:moderated_channels`
+///
+pub fn moderated_channels<'c>(
+    c: &'c TwitchClient,
+    user_id: &str,
+) -> TwitchResult<HelixIterator<'c, ModeratedChannel>> {
+    r#try!(c.require_scopes(&[Scope::UserReadModeratedChannels]));
+    Ok(HelixIterator::new(
+        c,
+        url("/moderation/channels", &[("user_id", user_id)]),
+    ))
+}
+
+/// Gets the current moderators and VIPs of a broadcaster's
+/// channel
+///
+/// #### Authentication: `moderation:read`, `channel:read:vips`
+///
+pub fn roster(c: &TwitchClient, broadcaster_id: &str) -> TwitchResult<Roster> {
+    let moderators = r#try!(r#try!(moderators(c, broadcaster_id, &[])).all());
+    let vips = r#try!(r#try!(vips(c, broadcaster_id, &[])).all());
+    Ok(Roster {
+        moderators: moderators.into_iter().map(|u| u.user_id).collect(),
+        vips: vips.into_iter().map(|u| u.user_id).collect(),
+    })
+}
+
+/// Adds and removes moderators and VIPs until the channel's
+/// roster matches `desired`
+///
+/// Removals are applied first, so a moderator can become a VIP
+/// and the other way round. A change that hits Twitch's limit of
+/// 10 changes per 10 seconds is retried once after the window,
+/// changes that still fail are reported in `RosterSync::failed`
+/// instead of stopping the others.
+///
+/// #### Authentication: `channel:manage:moderators`, `channel:manage:vips`
+///
+pub fn sync_roster(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    desired: &Roster,
+) -> TwitchResult<RosterSync> {
+    r#try!(desired.validate());
+    r#try!(c.require_scopes(&[Scope::ChannelManageModerators, Scope::ChannelManageVips]));
+    let changes = desired.changes(&r#try!(roster(c, broadcaster_id)));
+    let mut failed = Vec::new();
+    apply(&changes.remove_moderators, &mut failed, |id| {
+        remove_moderator(c, broadcaster_id, id)
+    });
+    apply(&changes.remove_vips, &mut failed, |id| {
+        remove_vip(c, broadcaster_id, id)
+    });
+    apply(&changes.add_moderators, &mut failed, |id| {
+        add_moderator(c, broadcaster_id, id)
+    });
+    apply(&changes.add_vips, &mut failed, |id| {
+        add_vip(c, broadcaster_id, id)
+    });
+    Ok(RosterSync {
+        changes: changes,
+        failed: failed,
+    })
+}
+
+fn apply<F>(ids: &[String], failed: &mut Vec<(String, ApiError)>, f: F)
+where
+    F: Fn(&str) -> TwitchResult<()>,
+{
+    for id in ids {
+        if let Err(e) = retry_rate_limited(|| ROSTER_WINDOW, || f(id)) {
+            failed.push((id.clone(), e));
+        }
+    }
+}
+
+/// Twitch allows 10 moderator or VIP changes per broadcaster in
+/// this window, apart from the client's rate limit budget
+const ROSTER_WINDOW: Duration = Duration::from_secs(10);
+
+/// Runs a request again once if it hit a rate limit, after
+/// waiting for the `window` that is current at that point
+fn retry_rate_limited<T, W, F>(window: W, f: F) -> TwitchResult<T>
+where
+    W: Fn() -> Duration,
+    F: Fn() -> TwitchResult<T>,
+{
+    match f() {
+        Err(ref e) if e.status() == Some(429) => {
+            thread::sleep(window());
+            f()
+        }
+        r => r,
+    }
+}
+
+/// Gets whether Shield Mode is active in a broadcaster's chat
+///
+/// Fails with `ApiError::Forbidden` unless `moderator_id` is
+/// the broadcaster or one of their moderators.
+///
+/// #### Authentication: `moderator:read:shield_mode`
+///
+pub fn shield_mode(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+) -> TwitchResult<ShieldModeStatus> {
+    r#try!(c.require_scopes(&[Scope::ModeratorReadShieldMode]));
+    let r = r#try!(
+        c.helix_get::<Data<ShieldModeStatus>>(&shield_mode_url(broadcaster_id, moderator_id))
+    );
+    first(r.data)
+}
+
+/// Activates or deactivates Shield Mode in a broadcaster's chat
+///
+/// Fails with `ApiError::Forbidden` unless `moderator_id` is
+/// the broadcaster or one of their moderators.
+///
+/// #### Authentication: `moderator:manage:shield_mode`
+///
+pub fn update_shield_mode(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    is_active: bool,
+) -> TwitchResult<ShieldModeStatus> {
+    r#try!(c.require_scopes(&[Scope::ModeratorManageShieldMode]));
+    let r = r#try!(c.helix_put::<UpdateShieldMode, Data<ShieldModeStatus>>(
+        &shield_mode_url(broadcaster_id, moderator_id),
+        &UpdateShieldMode {
+            is_active: is_active,
+        }
+    ));
+    first(r.data)
+}
+
+fn shield_mode_url(broadcaster_id: &str, moderator_id: &str) -> String {
+    url(
+        "/moderation/shield_mode",
+        &[
+            ("broadcaster_id", broadcaster_id),
+            ("moderator_id", moderator_id),
+        ],
+    )
+}
+
+/// Gets the unban requests with a status in a broadcaster's
+/// channel, optionally only those of one user
+///
+/// Fails with `ApiError::Forbidden` unless `moderator_id` is
+/// the broadcaster or one of their moderators.
+///
+/// #### Authentication: `moderator:read:unban_requests`
+///
+pub fn unban_requests<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    status: UnbanRequestStatus,
+    user_id: Option<&str>,
+) -> TwitchResult<HelixIterator<'c, UnbanRequest>> {
+    r#try!(c.require_scopes(&[Scope::ModeratorReadUnbanRequests]));
+    let status = status.to_string();
+    let mut params = vec![
+        ("broadcaster_id", broadcaster_id),
+        ("moderator_id", moderator_id),
+        ("status", status.as_str()),
+    ];
+    if let Some(user_id) = user_id {
+        params.push(("user_id", user_id));
+    }
+    Ok(HelixIterator::new(
+        c,
+        url("/moderation/unban_requests", &params),
+    ))
+}
+
+/// Approves or denies an unban request, optionally telling the
+/// user why
+///
+/// Fails with `ApiError::BadRequest` if the request was already
+/// resolved, and `ApiError::Forbidden` unless `moderator_id` is
+/// the broadcaster or one of their moderators.
+///
+/// #### Authentication: `moderator:manage:unban_requests`
+///
+pub fn resolve_unban_request(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    moderator_id: &str,
+    unban_request_id: &str,
+    status: UnbanResolution,
+    resolution_text: Option<&str>,
+) -> TwitchResult<UnbanRequest> {
+    if let Some(text) = resolution_text {
+        r#try!(check_reason(text));
+    }
+    r#try!(c.require_scopes(&[Scope::ModeratorManageUnbanRequests]));
+    let status = status.to_string();
+    let mut params = vec![
+        ("broadcaster_id", broadcaster_id),
+        ("moderator_id", moderator_id),
+        ("unban_request_id", unban_request_id),
+        ("status", status.as_str()),
+    ];
+    if let Some(text) = resolution_text {
+        params.push(("resolution_text", text));
+    }
+    let r = r#try!(
+        c.helix_patch::<(), Data<UnbanRequest>>(&url("/moderation/unban_requests", &params), &())
+    );
+    first(r.data)
+}
+
+fn check_reason(reason: &str) -> TwitchResult<()> {
+    if reason.chars().count() > MAX_REASON_LEN {
+        return Err(ApiError::invalid_request(&format!(
+            "Reasons may be at most {} characters long",
+            MAX_REASON_LEN
+        )));
+    }
+    Ok(())
+}
+
+///////////////////////////////////////
+// Ban
+///////////////////////////////////////
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Ban {
+    pub user_id: String,
+    /// Length of a timeout in seconds, `None` bans permanently
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Ban {
+    pub fn permanent(user_id: &str) -> Ban {
+        Ban {
+            user_id: user_id.to_owned(),
+            duration: None,
+            reason: None,
+        }
+    }
+
+    pub fn timeout(user_id: &str, duration: u32) -> Ban {
+        Ban {
+            user_id: user_id.to_owned(),
+            duration: Some(duration),
+            reason: None,
+        }
+    }
+
+    pub fn reason(mut self, reason: &str) -> Ban {
+        self.reason = Some(reason.to_owned());
+        self
+    }
+
+    /// Checks the limits Twitch enforces on bans
+    ///
+    /// Timeouts last 1 second to two weeks, reasons are at
+    /// most 500 characters.
+    ///
+    pub fn validate(&self) -> TwitchResult<()> {
+        if let Some(duration) = self.duration {
+            if duration < 1 || duration > MAX_TIMEOUT {
+                return Err(ApiError::invalid_request(&format!(
+                    "Timeouts have to last 1 to {} seconds",
+                    MAX_TIMEOUT
+                )));
+            }
+        }
+        if let Some(ref reason) = self.reason {
+            r#try!(check_reason(reason));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct BanRequest<'a> {
+    data: &'a Ban,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BannedUser {
+    pub broadcaster_id: String,
+    pub moderator_id: String,
+    pub user_id: String,
+    pub created_at: DateTime<UTC>,
+    /// When a timeout ends, `None` for permanent bans
+    pub end_time: Option<DateTime<UTC>>,
+}
+
+/// The outcome of `ban_all`
+#[derive(Debug)]
+pub struct BulkBan {
+    pub banned: Vec<BannedUser>,
+    /// The user ID of every ban that failed and why
+    pub failed: Vec<(String, ApiError)>,
+}
+
+///////////////////////////////////////
+// BannedUserInfo
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct BannedUserInfo {
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    /// When a timeout ends, `None` for permanent bans
+    #[serde(default, deserialize_with = "empty_as_none_time")]
+    pub expires_at: Option<DateTime<UTC>>,
+    pub created_at: DateTime<UTC>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub reason: Option<String>,
+    pub moderator_id: String,
+    pub moderator_login: String,
+    pub moderator_name: String,
+}
+
+///////////////////////////////////////
+// Warning
+///////////////////////////////////////
+#[derive(Serialize, Debug)]
+struct WarnRequest<'a> {
+    data: WarnData<'a>,
+}
+
+#[derive(Serialize, Debug)]
+struct WarnData<'a> {
+    user_id: &'a str,
+    reason: &'a str,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Warning {
+    pub broadcaster_id: String,
+    pub user_id: String,
+    pub moderator_id: String,
+    pub reason: String,
+}
+
+///////////////////////////////////////
+// AutoMod
+///////////////////////////////////////
+/// A message to run through `check_automod`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct AutoModCheck {
+    /// Any ID to match the message with its `AutoModStatus`
+    pub msg_id: String,
+    pub msg_text: String,
+}
+
+#[derive(Serialize, Debug)]
+struct AutoModCheckRequest<'a> {
+    data: &'a [AutoModCheck],
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct AutoModStatus {
+    pub msg_id: String,
+    /// Whether AutoMod would let the message through
+    pub is_permitted: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum HeldMessageAction {
+    #[serde(rename = "ALLOW")]
+    Allow,
+    #[serde(rename = "DENY")]
+    Deny,
+}
+
+#[derive(Serialize, Debug)]
+struct HeldMessage<'a> {
+    user_id: &'a str,
+    msg_id: &'a str,
+    action: HeldMessageAction,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct AutoModSettings {
+    pub broadcaster_id: String,
+    pub moderator_id: String,
+    /// `None` if the categories were set individually
+    pub overall_level: Option<u8>,
+    pub disability: u8,
+    pub aggression: u8,
+    pub sexuality_sex_or_gender: u8,
+    pub misogyny: u8,
+    pub bullying: u8,
+    pub swearing: u8,
+    pub race_ethnicity_or_religion: u8,
+    pub sex_based_terms: u8,
+}
+
+impl AutoModSettings {
+    /// The current category levels, to change some of them with
+    /// `update_automod_settings`
+    pub fn levels(&self) -> AutoModLevels {
+        AutoModLevels {
+            disability: self.disability,
+            aggression: self.aggression,
+            sexuality_sex_or_gender: self.sexuality_sex_or_gender,
+            misogyny: self.misogyny,
+            bullying: self.bullying,
+            swearing: self.swearing,
+            race_ethnicity_or_religion: self.race_ethnicity_or_religion,
+            sex_based_terms: self.sex_based_terms,
+        }
+    }
+}
+
+/// The level of each AutoMod category, from 0 (off) to 4
+/// (most filtering)
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct AutoModLevels {
+    pub disability: u8,
+    pub aggression: u8,
+    pub sexuality_sex_or_gender: u8,
+    pub misogyny: u8,
+    pub bullying: u8,
+    pub swearing: u8,
+    pub race_ethnicity_or_religion: u8,
+    pub sex_based_terms: u8,
+}
+
+impl AutoModLevels {
+    pub fn validate(&self) -> TwitchResult<()> {
+        let levels = [
+            ("disability", self.disability),
+            ("aggression", self.aggression),
+            ("sexuality_sex_or_gender", self.sexuality_sex_or_gender),
+            ("misogyny", self.misogyny),
+            ("bullying", self.bullying),
+            ("swearing", self.swearing),
+            (
+                "race_ethnicity_or_religion",
+                self.race_ethnicity_or_religion,
+            ),
+            ("sex_based_terms", self.sex_based_terms),
+        ];
+        for &(name, level) in levels.iter() {
+            r#try!(check_level(name, level));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct OverallLevel {
+    overall_level: u8,
+}
+
+///////////////////////////////////////
+// BlockedTerm
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct BlockedTerm {
+    pub broadcaster_id: String,
+    pub moderator_id: String,
+    pub id: String,
+    pub text: String,
+    pub created_at: DateTime<UTC>,
+    pub updated_at: DateTime<UTC>,
+    /// `None` for terms that are blocked until removed
+    pub expires_at: Option<DateTime<UTC>>,
+}
+
+#[derive(Serialize, Debug)]
+struct NewBlockedTerm<'a> {
+    text: &'a str,
+}
+
+///////////////////////////////////////
+// Roster
+///////////////////////////////////////
+/// A moderator or VIP
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RosterUser {
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ModeratedChannel {
+    pub broadcaster_id: String,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+}
+
+/// The user IDs of a channel's moderators and VIPs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Roster {
+    pub moderators: HashSet<String>,
+    pub vips: HashSet<String>,
+}
+
+impl Roster {
+    /// Fails if a user is both moderator and VIP, which
+    /// Twitch does not allow
+    pub fn validate(&self) -> TwitchResult<()> {
+        if let Some(id) = self.moderators.intersection(&self.vips).next() {
+            return Err(ApiError::invalid_request(&format!(
+                "User {} cannot be both moderator and VIP",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    /// What has to change to get from `current` to this roster
+    pub fn changes(&self, current: &Roster) -> RosterChanges {
+        RosterChanges {
+            add_moderators: sorted(self.moderators.difference(&current.moderators)),
+            remove_moderators: sorted(current.moderators.difference(&self.moderators)),
+            add_vips: sorted(self.vips.difference(&current.vips)),
+            remove_vips: sorted(current.vips.difference(&self.vips)),
+        }
+    }
+}
+
+fn sorted<'a, I: Iterator<Item = &'a String>>(ids: I) -> Vec<String> {
+    let mut ids: Vec<String> = ids.cloned().collect();
+    ids.sort();
+    ids
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RosterChanges {
+    pub add_moderators: Vec<String>,
+    pub remove_moderators: Vec<String>,
+    pub add_vips: Vec<String>,
+    pub remove_vips: Vec<String>,
+}
+
+impl RosterChanges {
+    pub fn is_empty(&self) -> bool {
+        self.add_moderators.is_empty()
+            && self.remove_moderators.is_empty()
+            && self.add_vips.is_empty()
+            && self.remove_vips.is_empty()
+    }
+}
+
+/// The outcome of `sync_roster`
+#[derive(Debug)]
+pub struct RosterSync {
+    /// Every change that was attempted
+    pub changes: RosterChanges,
+    /// The user ID of every change that failed and why
+    pub failed: Vec<(String, ApiError)>,
+}
+
+///////////////////////////////////////
+// ShieldModeStatus
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct ShieldModeStatus {
+    pub is_active: bool,
+    /// The moderator that last activated Shield Mode, `None` if
+    /// it was never active
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub moderator_id: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub moderator_login: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub moderator_name: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none_time")]
+    pub last_activated_at: Option<DateTime<UTC>>,
+}
+
+#[derive(Serialize, Debug)]
+struct UpdateShieldMode {
+    is_active: bool,
+}
+
+///////////////////////////////////////
+// UnbanRequest
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct UnbanRequest {
+    pub id: String,
+    pub broadcaster_id: String,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    /// The moderator that resolved the request, `None` while
+    /// it is pending
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub moderator_id: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub moderator_login: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub moderator_name: Option<String>,
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    /// What the user wrote in the request
+    pub text: String,
+    pub status: UnbanRequestStatus,
+    pub created_at: DateTime<UTC>,
+    #[serde(default, deserialize_with = "empty_as_none_time")]
+    pub resolved_at: Option<DateTime<UTC>>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub resolution_text: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum UnbanRequestStatus {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "approved")]
+    Approved,
+    #[serde(rename = "denied")]
+    Denied,
+    /// The user acknowledged a denied request
+    #[serde(rename = "acknowledged")]
+    Acknowledged,
+    /// The user withdrew the request
+    #[serde(rename = "canceled")]
+    Canceled,
+}
+
+impl fmt::Display for UnbanRequestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            UnbanRequestStatus::Pending => "pending",
+            UnbanRequestStatus::Approved => "approved",
+            UnbanRequestStatus::Denied => "denied",
+            UnbanRequestStatus::Acknowledged => "acknowledged",
+            UnbanRequestStatus::Canceled => "canceled",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnbanResolution {
+    Approved,
+    Denied,
+}
+
+impl fmt::Display for UnbanResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            UnbanResolution::Approved => "approved",
+            UnbanResolution::Denied => "denied",
+        })
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::super::new;
+    use super::super::super::response::{ApiError, ErrorResponse};
+    use super::super::Data;
+    use super::{
+        AutoModCheck, AutoModLevels, AutoModSettings, AutoModStatus, Ban, BannedUser,
+        BannedUserInfo, BlockedTerm, HeldMessageAction, ModeratedChannel, Roster, RosterChanges,
+        RosterUser, ShieldModeStatus, UnbanRequest, UnbanRequestStatus, UnbanResolution,
+        MAX_TIMEOUT,
+    };
+
+    use std::cell::{Cell, RefCell};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn ban() {
+        assert_eq!(
+            serde_json::to_string(&Ban::permanent("9876")).unwrap(),
+            r#"{"user_id":"9876"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Ban::timeout("9876", 300).reason("no spam")).unwrap(),
+            r#"{"user_id":"9876","duration":300,"reason":"no spam"}"#
+        );
+        assert!(Ban::timeout("9876", MAX_TIMEOUT).validate().is_ok());
+        assert!(Ban::timeout("9876", 0).validate().is_err());
+        assert!(Ban::timeout("9876", MAX_TIMEOUT + 1).validate().is_err());
+        assert!(Ban::permanent("9876")
+            .reason(&"x".repeat(501))
+            .validate()
+            .is_err());
+
+        let c = new(String::from("cid"));
+        match super::ban(&c, "1234", "5678", &Ban::timeout("9876", 0)) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+        match super::warn(&c, "1234", "5678", "9876", " ") {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+
+        let r: Data<BannedUser> = serde_json::from_str(
+            r#"{"data":[{"broadcaster_id":"1234","moderator_id":"5678","user_id":"9876","created_at":"2021-09-28T19:27:31Z","end_time":"2021-09-28T19:22:31Z"},{"broadcaster_id":"1234","moderator_id":"5678","user_id":"5432","created_at":"2021-09-28T19:27:31Z","end_time":null}]}"#,
+        )
+        .unwrap();
+        assert!(r.data[0].end_time.is_some());
+        assert!(r.data[1].end_time.is_none());
+    }
+
+    #[test]
+    fn banned_users() {
+        let r: Data<BannedUserInfo> = serde_json::from_str(
+            r#"{"data":[{"user_id":"423374343","user_login":"glowillig","user_name":"glowillig","expires_at":"2022-03-15T02:00:28Z","created_at":"2022-03-15T01:30:28Z","reason":"Does not like pineapple on pizza.","moderator_id":"141981764","moderator_login":"twitchdev","moderator_name":"TwitchDev"},{"user_id":"424596340","user_login":"quotrok","user_name":"quotrok","expires_at":"","created_at":"2022-08-07T02:07:55Z","reason":"","moderator_id":"141981764","moderator_login":"twitchdev","moderator_name":"TwitchDev"}],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjp7IkN1cnNvciI6IjEwMDQ3MzA2NDo4NjQwNjU3MToxSVZCVDFKMnY5M1BTOXh3d1E0dUdXMkJOMFcifX0"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            r.data[0].reason.as_deref(),
+            Some("Does not like pineapple on pizza.")
+        );
+        assert!(r.data[0].expires_at.is_some());
+        assert!(r.data[1].expires_at.is_none());
+        assert!(r.data[1].reason.is_none());
+        assert!(r.pagination.cursor.is_some());
+    }
+
+    #[test]
+    fn automod() {
+        let check = AutoModCheck {
+            msg_id: String::from("123"),
+            msg_text: String::from("Hello World!"),
+        };
+        assert_eq!(
+            serde_json::to_string(&super::AutoModCheckRequest { data: &[check] }).unwrap(),
+            r#"{"data":[{"msg_id":"123","msg_text":"Hello World!"}]}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&HeldMessageAction::Deny).unwrap(),
+            r#""DENY""#
+        );
+
+        let r: Data<AutoModStatus> =
+            serde_json::from_str(r#"{"data":[{"msg_id":"123","is_permitted":true},{"msg_id":"393","is_permitted":false}]}"#)
+                .unwrap();
+        assert!(!r.data[1].is_permitted);
+
+        let r: Data<AutoModSettings> = serde_json::from_str(
+            r#"{"data":[{"broadcaster_id":"1234","moderator_id":"5678","overall_level":null,"disability":0,"aggression":0,"sexuality_sex_or_gender":0,"misogyny":0,"bullying":0,"swearing":0,"race_ethnicity_or_religion":0,"sex_based_terms":0}]}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].overall_level, None);
+        assert_eq!(r.data[0].levels(), AutoModLevels::default());
+
+        let levels = AutoModLevels {
+            swearing: 5,
+            ..AutoModLevels::default()
+        };
+        let c = new(String::from("cid"));
+        match super::update_automod_settings(&c, "1234", "5678", &levels) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+        match super::update_automod_level(&c, "1234", "5678", 5) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn blocked_terms() {
+        let r: Data<BlockedTerm> = serde_json::from_str(
+            r#"{"data":[{"broadcaster_id":"1234","moderator_id":"5678","id":"520e4d4e-0cda-49c7-821e-e5ef4f88c2f2","text":"A phrase I'm not fond of","created_at":"2021-09-29T19:45:37Z","updated_at":"2021-09-29T19:45:37Z","expires_at":null}],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjp7IkN1cnNvciI6I"}}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].text, "A phrase I'm not fond of");
+        assert!(r.data[0].expires_at.is_none());
+
+        let c = new(String::from("cid"));
+        match super::add_blocked_term(&c, "1234", "5678", "a") {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn roster() {
+        let r: Data<RosterUser> = serde_json::from_str(
+            r#"{"data":[{"user_id":"424596340","user_login":"quotrok","user_name":"quotrok"},{"user_id":"146365386","user_login":"thindrake","user_name":"thindrake"}],"pagination":{}}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[1].user_login, "thindrake");
+
+        let r: Data<ModeratedChannel> = serde_json::from_str(
+            r#"{"data":[{"broadcaster_id":"12345","broadcaster_login":"grateful_broadcaster","broadcaster_name":"Grateful_Broadcaster"}],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjp7IkN1cnNvciI6"}}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].broadcaster_id, "12345");
+
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect();
+        let current = Roster {
+            moderators: ids(&["1", "2"]),
+            vips: ids(&["3"]),
+        };
+        let desired = Roster {
+            moderators: ids(&["2", "3"]),
+            vips: ids(&["1", "4"]),
+        };
+        assert_eq!(
+            desired.changes(&current),
+            RosterChanges {
+                add_moderators: vec![String::from("3")],
+                remove_moderators: vec![String::from("1")],
+                add_vips: vec![String::from("1"), String::from("4")],
+                remove_vips: vec![String::from("3")],
+            }
+        );
+        assert!(current.changes(&current).is_empty());
+
+        let c = new(String::from("cid"));
+        let invalid = Roster {
+            moderators: ids(&["1"]),
+            vips: ids(&["1"]),
+        };
+        match super::sync_roster(&c, "1234", &invalid) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    fn rate_limited() -> ApiError {
+        ApiError::TwitchError(ErrorResponse {
+            error: String::from("Too Many Requests"),
+            status: 429,
+            message: String::new(),
+            cause: None,
+        })
+    }
+
+    #[test]
+    fn ban_each() {
+        let r: Data<BannedUser> = serde_json::from_str(
+            r#"{"data":[{"broadcaster_id":"1234","moderator_id":"5678","user_id":"9876","created_at":"2021-09-28T19:27:31Z","end_time":null}]}"#,
+        )
+        .unwrap();
+        let banned = r.data[0].clone();
+        let calls = RefCell::new(Vec::new());
+        let r = super::ban_each(
+            &[Ban::permanent("1"), Ban::permanent("2")],
+            || Duration::from_millis(0),
+            |b| {
+                calls.borrow_mut().push(b.user_id.clone());
+                let n = calls.borrow().iter().filter(|id| *id == &b.user_id).count();
+                // the first ban goes through on its retry, the second never
+                if b.user_id == "1" && n == 2 {
+                    return Ok(banned.clone());
+                }
+                Err(rate_limited())
+            },
+        );
+        assert_eq!(*calls.borrow(), vec!["1", "1", "2", "2"]);
+        assert_eq!(r.banned.len(), 1);
+        assert_eq!(r.failed.len(), 1);
+        assert_eq!(r.failed[0].0, "2");
+        assert_eq!(r.failed[0].1.status(), Some(429));
+    }
+
+    #[test]
+    fn retry_rate_limited() {
+        let calls = Cell::new(0);
+        let start = Instant::now();
+        let r = super::retry_rate_limited(
+            || Duration::from_millis(200),
+            || {
+                calls.set(calls.get() + 1);
+                if calls.get() == 1 {
+                    return Err(rate_limited());
+                }
+                Ok(calls.get())
+            },
+        );
+        assert_eq!(r.unwrap(), 2);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        calls.set(0);
+        let r: Result<(), _> = super::retry_rate_limited(
+            || Duration::from_secs(10),
+            || {
+                calls.set(calls.get() + 1);
+                Err(ApiError::invalid_request("no"))
+            },
+        );
+        assert!(r.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn shield_mode() {
+        let r: Data<ShieldModeStatus> = serde_json::from_str(
+            r#"{"data":[{"is_active":true,"moderator_id":"98765","moderator_name":"SimplySimple","moderator_login":"simplysimple","last_activated_at":"2022-07-26T17:16:03.123Z"}]}"#,
+        )
+        .unwrap();
+        assert!(r.data[0].is_active);
+        assert_eq!(r.data[0].moderator_login.as_deref(), Some("simplysimple"));
+        assert!(r.data[0].last_activated_at.is_some());
+
+        let r: Data<ShieldModeStatus> = serde_json::from_str(
+            r#"{"data":[{"is_active":false,"moderator_id":"","moderator_name":"","moderator_login":"","last_activated_at":""}]}"#,
+        )
+        .unwrap();
+        assert!(r.data[0].moderator_id.is_none());
+        assert!(r.data[0].last_activated_at.is_none());
+    }
+
+    #[test]
+    fn unban_requests() {
+        let r: Data<UnbanRequest> = serde_json::from_str(
+            r#"{"data":[{"id":"92af127c-7326-4483-a52b-b0da0be61c01","broadcaster_name":"awesome_broadcaster","broadcaster_login":"awesome_broadcaster","broadcaster_id":"107124928","moderator_id":"","moderator_login":"","moderator_name":"","user_id":"141981764","user_login":"twitchdev","user_name":"TwitchDev","text":"Please unban me from the channel?","status":"pending","created_at":"2024-02-17T14:31:12.583Z","resolved_at":null,"resolution_text":null}],"pagination":{"cursor":"eyJiIjpudWxsLCJhIjp7IkN1cnNvciI6"}}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].status, UnbanRequestStatus::Pending);
+        assert!(r.data[0].moderator_id.is_none());
+        assert!(r.data[0].resolved_at.is_none());
+        assert!(r.data[0].resolution_text.is_none());
+
+        let r: Data<UnbanRequest> = serde_json::from_str(
+            r#"{"data":[{"id":"92af127c-7326-4483-a52b-b0da0be61c01","broadcaster_name":"awesome_broadcaster","broadcaster_login":"awesome_broadcaster","broadcaster_id":"107124928","moderator_id":"141981764","moderator_login":"twitchdev","moderator_name":"TwitchDev","user_id":"141981764","user_login":"twitchdev","user_name":"TwitchDev","text":"Please unban me from the channel?","status":"approved","created_at":"2024-02-17T14:31:12.583Z","resolved_at":"2024-02-17T14:32:12.583Z","resolution_text":"We forgive you"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].status, UnbanRequestStatus::Approved);
+        assert_eq!(r.data[0].resolution_text.as_deref(), Some("We forgive you"));
+        assert_eq!(UnbanRequestStatus::Acknowledged.to_string(), "acknowledged");
+        assert_eq!(UnbanResolution::Denied.to_string(), "denied");
+
+        let c = new(String::from("cid"));
+        match super::resolve_unban_request(
+            &c,
+            "1234",
+            "5678",
+            "92af",
+            UnbanResolution::Denied,
+            Some(&"x".repeat(501)),
+        ) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+}
@@ -0,0 +1,328 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use super::super::auth::Scope;
+use super::super::response::{ApiError, TwitchResult};
+use super::super::TwitchClient;
+use super::{check_len, first, url, Data, HelixIterator};
+
+/// At most this many polls can be requested by ID at once
+pub const MAX_POLL_IDS: usize = 20;
+
+pub const MIN_CHOICES: usize = 2;
+pub const MAX_CHOICES: usize = 5;
+
+/// Shortest and longest poll duration in seconds
+pub const MIN_DURATION: u32 = 15;
+pub const MAX_DURATION: u32 = 1800;
+
+pub const MAX_TITLE_LEN: usize = 60;
+pub const MAX_CHOICE_TITLE_LEN: usize = 25;
+pub const MAX_CHANNEL_POINTS_PER_VOTE: u32 = 1_000_000;
+
+/// Gets the polls of a broadcaster, newest first, or only those
+/// of up to 20 `ids`
+///
+/// Polls are available for 90 days after they end.
+///
+/// #### Authentication: `channel:read:polls` or `channel:manage:polls`
+///
+pub fn get<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    ids: &[&str],
+) -> TwitchResult<HelixIterator<'c, Poll>> {
+    r#try!(check_len("id", ids, MAX_POLL_IDS));
+    let mut params = vec![("broadcaster_id", broadcaster_id)];
+    params.extend(ids.iter().map(|id| ("id", *id)));
+    Ok(HelixIterator::new(c, url("/polls", &params)))
+}
+
+/// Starts a poll in a broadcaster's channel
+///
+/// Fails with `ApiError::BadRequest` while another poll is
+/// running.
+///
+/// #### Authentication: `channel:manage:polls`
+///
+pub fn create(c: &TwitchClient, poll: &CreatePoll) -> TwitchResult<Poll> {
+    r#try!(poll.validate());
+    r#try!(c.require_scopes(&[Scope::ChannelManagePolls]));
+    let r = r#try!(c.helix_post::<CreatePoll, Data<Poll>>("/polls", poll));
+    first(r.data)
+}
+
+/// Ends an active poll, either showing its result to viewers
+/// (`Terminated`) or hiding it (`Archived`)
+///
+/// #### Authentication: `channel:manage:polls`
+///
+pub fn end(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    id: &str,
+    status: EndPollStatus,
+) -> TwitchResult<Poll> {
+    r#try!(c.require_scopes(&[Scope::ChannelManagePolls]));
+    let r = r#try!(c.helix_patch::<EndPoll, Data<Poll>>(
+        "/polls",
+        &EndPoll {
+            broadcaster_id: broadcaster_id,
+            id: id,
+            status: status,
+        }
+    ));
+    first(r.data)
+}
+
+///////////////////////////////////////
+// Poll
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Poll {
+    pub id: String,
+    pub broadcaster_id: String,
+    pub broadcaster_name: String,
+    pub broadcaster_login: String,
+    pub title: String,
+    pub choices: Vec<PollChoice>,
+    pub channel_points_voting_enabled: bool,
+    pub channel_points_per_vote: u32,
+    pub status: PollStatus,
+    /// Length of the poll in seconds
+    pub duration: u32,
+    pub started_at: DateTime<UTC>,
+    /// `None` while the poll is active
+    pub ended_at: Option<DateTime<UTC>>,
+}
+
+impl Poll {
+    pub fn total_votes(&self) -> i64 {
+        self.choices.iter().map(|c| c.votes).sum()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PollChoice {
+    pub id: String,
+    pub title: String,
+    /// All votes, including those bought with channel points
+    pub votes: i64,
+    pub channel_points_votes: i64,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PollStatus {
+    #[serde(rename = "ACTIVE")]
+    Active,
+    #[serde(rename = "COMPLETED")]
+    Completed,
+    /// Ended early, the result is shown to viewers
+    #[serde(rename = "TERMINATED")]
+    Terminated,
+    /// Ended early, the result is hidden from viewers
+    #[serde(rename = "ARCHIVED")]
+    Archived,
+    /// Deleted by Twitch
+    #[serde(rename = "MODERATED")]
+    Moderated,
+    #[serde(rename = "INVALID")]
+    Invalid,
+}
+
+///////////////////////////////////////
+// CreatePoll
+///////////////////////////////////////
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CreatePoll {
+    pub broadcaster_id: String,
+    pub title: String,
+    pub choices: Vec<NewPollChoice>,
+    /// Length of the poll in seconds
+    pub duration: u32,
+    pub channel_points_voting_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_points_per_vote: Option<u32>,
+}
+
+impl CreatePoll {
+    pub fn new(broadcaster_id: &str, title: &str, choices: &[&str], duration: u32) -> CreatePoll {
+        CreatePoll {
+            broadcaster_id: broadcaster_id.to_owned(),
+            title: title.to_owned(),
+            choices: choices
+                .iter()
+                .map(|t| NewPollChoice {
+                    title: (*t).to_owned(),
+                })
+                .collect(),
+            duration: duration,
+            channel_points_voting_enabled: false,
+            channel_points_per_vote: None,
+        }
+    }
+
+    /// Lets viewers buy additional votes with channel points
+    pub fn channel_points(mut self, per_vote: u32) -> CreatePoll {
+        self.channel_points_voting_enabled = true;
+        self.channel_points_per_vote = Some(per_vote);
+        self
+    }
+
+    /// Checks the limits Twitch enforces on polls
+    ///
+    /// Polls have a title of 1 to 60 characters, 2 to 5 choices
+    /// of 1 to 25 characters and last 15 to 1800 seconds.
+    /// Channel points voting costs 1 to 1000000 points per vote.
+    ///
+    pub fn validate(&self) -> TwitchResult<()> {
+        r#try!(check_title("Poll titles", &self.title, MAX_TITLE_LEN));
+        if self.choices.len() < MIN_CHOICES || self.choices.len() > MAX_CHOICES {
+            return Err(ApiError::invalid_request(&format!(
+                "Polls have to have {} to {} choices, got {}",
+                MIN_CHOICES,
+                MAX_CHOICES,
+                self.choices.len()
+            )));
+        }
+        for choice in &self.choices {
+            r#try!(check_title(
+                "Choice titles",
+                &choice.title,
+                MAX_CHOICE_TITLE_LEN
+            ));
+        }
+        if self.duration < MIN_DURATION || self.duration > MAX_DURATION {
+            return Err(ApiError::invalid_request(&format!(
+                "Polls have to last {} to {} seconds",
+                MIN_DURATION, MAX_DURATION
+            )));
+        }
+        if self.channel_points_voting_enabled {
+            match self.channel_points_per_vote {
+                Some(points) if points >= 1 && points <= MAX_CHANNEL_POINTS_PER_VOTE => (),
+                _ => {
+                    return Err(ApiError::invalid_request(&format!(
+                        "Channel points voting costs 1 to {} points per vote",
+                        MAX_CHANNEL_POINTS_PER_VOTE
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_title(name: &str, title: &str, max: usize) -> TwitchResult<()> {
+    if title.trim().is_empty() || title.chars().count() > max {
+        return Err(ApiError::invalid_request(&format!(
+            "{} have to be 1 to {} characters long",
+            name, max
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct NewPollChoice {
+    pub title: String,
+}
+
+///////////////////////////////////////
+// EndPoll
+///////////////////////////////////////
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum EndPollStatus {
+    #[serde(rename = "TERMINATED")]
+    Terminated,
+    #[serde(rename = "ARCHIVED")]
+    Archived,
+}
+
+#[derive(Serialize, Debug)]
+struct EndPoll<'a> {
+    broadcaster_id: &'a str,
+    id: &'a str,
+    status: EndPollStatus,
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::super::new;
+    use super::super::super::response::ApiError;
+    use super::super::Data;
+    use super::{CreatePoll, EndPollStatus, Poll, PollStatus};
+
+    #[test]
+    fn poll() {
+        let r: Data<Poll> = serde_json::from_str(
+            r#"{"data":[{"id":"ed961efd-8a3f-4cf5-a9d0-e616c590cd2a","broadcaster_id":"55696719","broadcaster_name":"TwitchDev","broadcaster_login":"twitchdev","title":"Heads or Tails?","choices":[{"id":"4c123012-1351-4f33-84b7-43856e7a0f47","title":"Heads","votes":4,"channel_points_votes":1,"bits_votes":0},{"id":"279087e3-54a7-467e-bcd0-c1393fcea4f0","title":"Tails","votes":2,"channel_points_votes":0,"bits_votes":0}],"bits_voting_enabled":false,"bits_per_vote":0,"channel_points_voting_enabled":true,"channel_points_per_vote":100,"status":"TERMINATED","duration":1800,"started_at":"2021-03-19T06:08:33.871278372Z","ended_at":"2021-03-19T06:11:26.746889614Z"}],"pagination":{}}"#,
+        )
+        .unwrap();
+        let p = &r.data[0];
+        assert_eq!(p.status, PollStatus::Terminated);
+        assert_eq!(p.choices[0].channel_points_votes, 1);
+        assert_eq!(p.total_votes(), 6);
+        assert!(p.ended_at.is_some());
+
+        assert_eq!(
+            serde_json::to_string(&EndPollStatus::Archived).unwrap(),
+            r#""ARCHIVED""#
+        );
+    }
+
+    #[test]
+    fn create_poll() {
+        let poll = CreatePoll::new("141981764", "Heads or Tails?", &["Heads", "Tails"], 1800);
+        assert!(poll.validate().is_ok());
+        assert_eq!(
+            serde_json::to_string(&poll).unwrap(),
+            r#"{"broadcaster_id":"141981764","title":"Heads or Tails?","choices":[{"title":"Heads"},{"title":"Tails"}],"duration":1800,"channel_points_voting_enabled":false}"#
+        );
+        assert!(poll.clone().channel_points(100).validate().is_ok());
+        assert!(poll.clone().channel_points(0).validate().is_err());
+
+        let invalid = vec![
+            CreatePoll::new("1", "", &["a", "b"], 60),
+            CreatePoll::new("1", &"x".repeat(61), &["a", "b"], 60),
+            CreatePoll::new("1", "title", &["a"], 60),
+            CreatePoll::new("1", "title", &["a", "b", "c", "d", "e", "f"], 60),
+            CreatePoll::new("1", "title", &["a", &"x".repeat(26)], 60),
+            CreatePoll::new("1", "title", &["a", "b"], 14),
+            CreatePoll::new("1", "title", &["a", "b"], 1801),
+        ];
+        for poll in &invalid {
+            assert!(poll.validate().is_err(), "{:?}", poll);
+        }
+
+        let c = new(String::from("cid"));
+        match super::create(&c, &invalid[0]) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+}
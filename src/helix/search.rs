@@ -0,0 +1,152 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use super::super::response::{ApiError, TwitchResult};
+use super::super::TwitchClient;
+use super::games::Game;
+use super::{empty_as_none_time, url, HelixIterator};
+
+/// Searches for games and categories whose name matches `query`
+///
+/// #### Authentication: `None`
+///
+pub fn categories<'c>(c: &'c TwitchClient, query: &str) -> TwitchResult<HelixIterator<'c, Game>> {
+    r#try!(check_query(query));
+    Ok(HelixIterator::new(
+        c,
+        url("/search/categories", &[("query", query)]),
+    ))
+}
+
+/// Searches for channels whose login or display name matches
+/// `query`, optionally only those that are live
+///
+/// #### Authentication: `None`
+///
+pub fn channels<'c>(
+    c: &'c TwitchClient,
+    query: &str,
+    live_only: bool,
+) -> TwitchResult<HelixIterator<'c, SearchChannel>> {
+    r#try!(check_query(query));
+    let live_only = live_only.to_string();
+    Ok(HelixIterator::new(
+        c,
+        url(
+            "/search/channels",
+            &[("query", query), ("live_only", &live_only)],
+        ),
+    ))
+}
+
+fn check_query(query: &str) -> TwitchResult<()> {
+    if query.trim().is_empty() {
+        return Err(ApiError::invalid_request("The search query is empty"));
+    }
+    Ok(())
+}
+
+///////////////////////////////////////
+// SearchChannel
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchChannel {
+    pub id: String,
+    pub broadcaster_login: String,
+    pub display_name: String,
+    pub broadcaster_language: String,
+    pub game_id: String,
+    pub game_name: String,
+    pub is_live: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The profile image of the broadcaster
+    pub thumbnail_url: String,
+    pub title: String,
+    /// `None` while the channel is offline
+    #[serde(default, deserialize_with = "empty_as_none_time")]
+    pub started_at: Option<DateTime<UTC>>,
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::super::new;
+    use super::super::super::response::ApiError;
+    use super::super::Data;
+    use super::SearchChannel;
+
+    #[test]
+    fn search_channel() {
+        let r: Data<SearchChannel> = serde_json::from_str(
+            r#"{"data":[{
+                "broadcaster_language":"en",
+                "broadcaster_login":"loserfruit",
+                "display_name":"Loserfruit",
+                "game_id":"498000",
+                "game_name":"House Flipper",
+                "id":"41245072",
+                "is_live":false,
+                "tag_ids":[],
+                "tags":["English"],
+                "thumbnail_url":"https://static-cdn.jtvnw.net/jtv_user_pictures/fd17325a-7dc2-46c6-8617-e90ec259501c-profile_image-300x300.png",
+                "title":"loserfruit",
+                "started_at":""
+            },{
+                "broadcaster_language":"en",
+                "broadcaster_login":"a_seagull",
+                "display_name":"A_Seagull",
+                "game_id":"506442",
+                "game_name":"DOOM Eternal",
+                "id":"19070311",
+                "is_live":true,
+                "tag_ids":[],
+                "tags":["English"],
+                "thumbnail_url":"https://static-cdn.jtvnw.net/jtv_user_pictures/a_seagull-profile_image-4d2d235688c7dc66-300x300.png",
+                "title":"1st ever rocket league tournament",
+                "started_at":"2020-03-18T17:56:00Z"
+            }],"pagination":{"cursor":"Mg=="}}"#,
+        )
+        .unwrap();
+        assert!(r.data[0].started_at.is_none());
+        assert_eq!(r.data[1].started_at.unwrap().timestamp(), 1584554160);
+
+        let r: SearchChannel = serde_json::from_str(
+            r#"{"broadcaster_language":"en","broadcaster_login":"loserfruit","display_name":"Loserfruit","game_id":"498000","game_name":"House Flipper","id":"41245072","is_live":false,"tags":[],"thumbnail_url":"","title":"loserfruit"}"#,
+        )
+        .unwrap();
+        assert!(r.started_at.is_none());
+    }
+
+    #[test]
+    fn empty_query() {
+        let c = new(String::from("cid"));
+        match super::channels(&c, " ", true) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+    }
+}
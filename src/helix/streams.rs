@@ -0,0 +1,195 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use super::super::auth::Scope;
+use super::super::response::TwitchResult;
+use super::super::TwitchClient;
+use super::{check_len, image_url, url, HelixIterator, MAX_IDS};
+
+use std::fmt;
+
+/// Gets active streams, optionally filtered
+///
+/// Streams are sorted by viewer count, highest first. The
+/// iterator pages forward; start it with `before` to page
+/// backward from a cursor instead.
+///
+/// #### Authentication: `None`
+///
+pub fn get<'c>(
+    c: &'c TwitchClient,
+    filter: &StreamFilter,
+) -> TwitchResult<HelixIterator<'c, Stream>> {
+    r#try!(check_len("user_id", &filter.user_ids, MAX_IDS));
+    r#try!(check_len("user_login", &filter.user_logins, MAX_IDS));
+    r#try!(check_len("game_id", &filter.game_ids, MAX_IDS));
+    r#try!(check_len("language", &filter.languages, MAX_IDS));
+
+    let stream_type = filter.stream_type.map(|t| t.to_string());
+    let mut params: Vec<(&str, &str)> = Vec::new();
+    params.extend(filter.user_ids.iter().map(|v| ("user_id", v.as_str())));
+    params.extend(
+        filter
+            .user_logins
+            .iter()
+            .map(|v| ("user_login", v.as_str())),
+    );
+    params.extend(filter.game_ids.iter().map(|v| ("game_id", v.as_str())));
+    params.extend(filter.languages.iter().map(|v| ("language", v.as_str())));
+    if let Some(ref t) = stream_type {
+        params.push(("type", t));
+    }
+    Ok(HelixIterator::new(c, url("/streams", &params)))
+}
+
+/// Gets the live streams of the channels a user follows
+///
+/// `user_id` has to match the user the OAuth token belongs to.
+///
+/// #### Authentication: `user:read:follows`
+///
+pub fn followed<'c>(c: &'c TwitchClient, user_id: &str) -> TwitchResult<HelixIterator<'c, Stream>> {
+    r#try!(c.require_scopes(&[Scope::UserReadFollows]));
+    Ok(HelixIterator::new(
+        c,
+        url("/streams/followed", &[("user_id", user_id)]),
+    ))
+}
+
+///////////////////////////////////////
+// StreamFilter
+///////////////////////////////////////
+/// Filters for `get`, each list takes at most 100 values
+#[derive(Debug, Clone, Default)]
+pub struct StreamFilter {
+    pub user_ids: Vec<String>,
+    pub user_logins: Vec<String>,
+    pub game_ids: Vec<String>,
+    pub stream_type: Option<StreamType>,
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamType {
+    All,
+    Live,
+}
+
+impl fmt::Display for StreamType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StreamType::All => write!(f, "all"),
+            StreamType::Live => write!(f, "live"),
+        }
+    }
+}
+
+///////////////////////////////////////
+// Stream
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Stream {
+    pub id: String,
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    pub game_id: String,
+    pub game_name: String,
+    /// `live`, or empty if something went wrong
+    #[serde(rename = "type")]
+    pub stream_type: String,
+    pub title: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub viewer_count: i64,
+    pub started_at: DateTime<UTC>,
+    pub language: String,
+    /// Contains `{width}` and `{height}` placeholders,
+    /// see `thumbnail`
+    pub thumbnail_url: String,
+    pub is_mature: bool,
+}
+
+impl Stream {
+    /// The thumbnail URL for the given size
+    pub fn thumbnail(&self, width: u32, height: u32) -> String {
+        image_url(&self.thumbnail_url, width, height)
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::super::new;
+    use super::super::super::response::ApiError;
+    use super::super::Data;
+    use super::{Stream, StreamFilter};
+
+    #[test]
+    fn stream() {
+        let r: Data<Stream> = serde_json::from_str(
+            r#"{"data":[{
+                "id":"40952121085",
+                "user_id":"101051819",
+                "user_login":"afro",
+                "user_name":"Afro",
+                "game_id":"32982",
+                "game_name":"Grand Theft Auto V",
+                "type":"live",
+                "title":"Jacob: Digital Den Laptops & Routers | NoPixel | !MAINGEAR !FCF",
+                "tags":["English"],
+                "viewer_count":1490,
+                "started_at":"2021-03-10T03:18:11Z",
+                "language":"en",
+                "thumbnail_url":"https://static-cdn.jtvnw.net/previews-ttv/live_user_afro-{width}x{height}.jpg",
+                "tag_ids":[],
+                "is_mature":false
+            }],"pagination":{"cursor":"eyJiIjp7IkN1cnNvciI6ImV5SnpJam8zT0RNMk5TNDBORFF4TlRjMU1UY3hOU3dpWkNJNlptRnNjMlVzSW5RaU9uUnlkV1Y5In0sImEiOnsiQ3Vyc29yIjoiIn19"}}"#,
+        )
+        .unwrap();
+        let stream = &r.data[0];
+        assert_eq!(stream.tags, vec!["English"]);
+        assert!(!stream.is_mature);
+        assert_eq!(
+            stream.thumbnail(320, 180),
+            "https://static-cdn.jtvnw.net/previews-ttv/live_user_afro-320x180.jpg"
+        );
+        assert!(r.pagination.cursor.is_some());
+    }
+
+    #[test]
+    fn filter_limits() {
+        let c = new(String::from("cid"));
+        let filter = StreamFilter {
+            languages: vec![String::from("en"); 101],
+            ..StreamFilter::default()
+        };
+        match super::get(&c, &filter) {
+            Err(ApiError::InvalidRequest(e)) => assert!(e.description.contains("language")),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+    }
+}
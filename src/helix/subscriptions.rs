@@ -0,0 +1,211 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+use super::super::auth::Scope;
+use super::super::response::TwitchResult;
+use super::super::TwitchClient;
+use super::{check_len, empty_as_none, first, url, Data, HelixIterator, MAX_IDS};
+
+use std::fmt;
+
+/// Gets the subscribers of a broadcaster
+///
+/// With `user_ids`, only the subscriptions of up to 100 of
+/// those users are returned, on a single page.
+///
+/// #### Authentication: `channel:read:subscriptions`
+///
+pub fn broadcaster_subscriptions<'c>(
+    c: &'c TwitchClient,
+    broadcaster_id: &str,
+    user_ids: &[&str],
+) -> TwitchResult<HelixIterator<'c, Subscription>> {
+    r#try!(check_len("user_id", user_ids, MAX_IDS));
+    r#try!(c.require_scopes(&[Scope::ChannelReadSubscriptions]));
+    let mut params = vec![("broadcaster_id", broadcaster_id)];
+    params.extend(user_ids.iter().map(|id| ("user_id", *id)));
+    Ok(HelixIterator::new(c, url("/subscriptions", &params)))
+}
+
+/// Gets the number of subscribers and subscriber points of
+/// a broadcaster
+///
+/// #### Authentication: `channel:read:subscriptions`
+///
+pub fn totals(c: &TwitchClient, broadcaster_id: &str) -> TwitchResult<SubscriptionTotals> {
+    r#try!(c.require_scopes(&[Scope::ChannelReadSubscriptions]));
+    let r = r#try!(c.helix_get::<SubscriptionTotals>(&url(
+        "/subscriptions",
+        &[("broadcaster_id", broadcaster_id), ("first", "1")]
+    )));
+    Ok(r)
+}
+
+/// Checks whether a user subscribes to a broadcaster
+///
+/// `user_id` has to match the user the OAuth token belongs to.
+/// Returns `None` if the user is not subscribed.
+///
+/// #### Authentication: `user:read:subscriptions`
+///
+pub fn check(
+    c: &TwitchClient,
+    broadcaster_id: &str,
+    user_id: &str,
+) -> TwitchResult<Option<UserSubscription>> {
+    r#try!(c.require_scopes(&[Scope::UserReadSubscriptions]));
+    let r = c.helix_get::<Data<UserSubscription>>(&url(
+        "/subscriptions/user",
+        &[("broadcaster_id", broadcaster_id), ("user_id", user_id)],
+    ));
+    match r {
+        Ok(r) => first(r.data).map(Some),
+        Err(ref e) if e.status() == Some(404) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+///////////////////////////////////////
+// Subscription
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Subscription {
+    pub broadcaster_id: String,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    pub tier: Tier,
+    pub plan_name: String,
+    pub is_gift: bool,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub gifter_id: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub gifter_login: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub gifter_name: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserSubscription {
+    pub broadcaster_id: String,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub tier: Tier,
+    pub is_gift: bool,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub gifter_id: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub gifter_login: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub gifter_name: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SubscriptionTotals {
+    /// The number of subscribers
+    pub total: i64,
+    /// Subscriber points, where tier 1 counts 1, tier 2
+    /// counts 2 and tier 3 counts 6
+    pub points: i64,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Tier {
+    #[serde(rename = "1000")]
+    Tier1,
+    #[serde(rename = "2000")]
+    Tier2,
+    #[serde(rename = "3000")]
+    Tier3,
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Tier::Tier1 => write!(f, "1000"),
+            Tier::Tier2 => write!(f, "2000"),
+            Tier::Tier3 => write!(f, "3000"),
+        }
+    }
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::Data;
+    use super::{Subscription, SubscriptionTotals, Tier, UserSubscription};
+
+    #[test]
+    fn subscriptions() {
+        let json = r#"{"data":[{
+                "broadcaster_id":"141981764",
+                "broadcaster_login":"twitchdev",
+                "broadcaster_name":"TwitchDev",
+                "gifter_id":"12826",
+                "gifter_login":"twitch",
+                "gifter_name":"Twitch",
+                "is_gift":true,
+                "tier":"1000",
+                "plan_name":"Channel Subscription (twitchdev)",
+                "user_id":"527115020",
+                "user_name":"twitchgaming",
+                "user_login":"twitchgaming"
+            },{
+                "broadcaster_id":"141981764",
+                "broadcaster_login":"twitchdev",
+                "broadcaster_name":"TwitchDev",
+                "gifter_id":"",
+                "gifter_login":"",
+                "gifter_name":"",
+                "is_gift":false,
+                "tier":"3000",
+                "plan_name":"Channel Subscription (twitchdev)",
+                "user_id":"12826",
+                "user_name":"Twitch",
+                "user_login":"twitch"
+            }],"pagination":{"cursor":"xxxx"},"total":13,"points":13}"#;
+
+        let r: Data<Subscription> = serde_json::from_str(json).unwrap();
+        assert_eq!(r.data[0].tier, Tier::Tier1);
+        assert_eq!(r.data[0].gifter_name.as_deref(), Some("Twitch"));
+        assert_eq!(r.data[1].tier, Tier::Tier3);
+        assert!(r.data[1].gifter_id.is_none());
+
+        let r: SubscriptionTotals = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            r,
+            SubscriptionTotals {
+                total: 13,
+                points: 13,
+            }
+        );
+
+        let r: Data<UserSubscription> = serde_json::from_str(
+            r#"{"data":[{"broadcaster_id":"141981764","broadcaster_name":"TwitchDev","broadcaster_login":"twitchdev","is_gift":false,"tier":"2000"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(r.data[0].tier, Tier::Tier2);
+        assert!(r.data[0].gifter_login.is_none());
+    }
+}
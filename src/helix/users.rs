@@ -0,0 +1,161 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use super::super::auth::Scope;
+use super::super::response::TwitchResult;
+use super::super::TwitchClient;
+use super::{first, url, Data, MAX_IDS};
+
+/// Gets users by ID and login name
+///
+/// Lists longer than 100 entries in total are split into
+/// several requests. With no IDs and no logins, gets the
+/// user the OAuth token belongs to. Users that do not exist
+/// are left out of the result.
+///
+/// #### Authentication: App access token or user access token, `user:read:email` to include `email`
+///
+pub fn get(c: &TwitchClient, ids: &[&str], logins: &[&str]) -> TwitchResult<Vec<User>> {
+    let params: Vec<(&str, &str)> = ids
+        .iter()
+        .map(|id| ("id", *id))
+        .chain(logins.iter().map(|login| ("login", *login)))
+        .collect();
+    if params.is_empty() {
+        let r = r#try!(c.helix_get::<Data<User>>("/users"));
+        return Ok(r.data);
+    }
+    let mut users = Vec::with_capacity(params.len());
+    for batch in params.chunks(MAX_IDS) {
+        let r = r#try!(c.helix_get::<Data<User>>(&url("/users", batch)));
+        users.extend(r.data);
+    }
+    Ok(users)
+}
+
+/// Gets a single user by login name
+///
+/// #### Authentication: App access token or user access token
+///
+pub fn get_by_login(c: &TwitchClient, login: &str) -> TwitchResult<Option<User>> {
+    let mut r = r#try!(get(c, &[], &[login]));
+    Ok(r.pop())
+}
+
+/// Updates the description of the user the OAuth token belongs to
+///
+/// #### Authentication: `user:edit`
+///
+pub fn update(c: &TwitchClient, description: &str) -> TwitchResult<User> {
+    r#try!(c.require_scopes(&[Scope::UserEdit]));
+    let r =
+        r#try!(c.helix_put::<(), Data<User>>(&url("/users", &[("description", description)]), &()));
+    first(r.data)
+}
+
+///////////////////////////////////////
+// User
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct User {
+    pub id: String,
+    pub login: String,
+    pub display_name: String,
+    #[serde(rename = "type")]
+    pub user_type: UserType,
+    pub broadcaster_type: BroadcasterType,
+    pub description: String,
+    pub profile_image_url: String,
+    pub offline_image_url: String,
+    /// Only set with the `user:read:email` scope
+    pub email: Option<String>,
+    pub created_at: DateTime<UTC>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum UserType {
+    #[serde(rename = "admin")]
+    Admin,
+    #[serde(rename = "global_mod")]
+    GlobalMod,
+    #[serde(rename = "staff")]
+    Staff,
+    #[serde(rename = "")]
+    Normal,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BroadcasterType {
+    #[serde(rename = "partner")]
+    Partner,
+    #[serde(rename = "affiliate")]
+    Affiliate,
+    #[serde(rename = "")]
+    Normal,
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::super::super::response::ApiError;
+    use super::super::{first, Data};
+    use super::{BroadcasterType, User, UserType};
+
+    #[test]
+    fn user() {
+        let r: Data<User> = serde_json::from_str(
+            r#"{"data":[{
+                "id":"141981764",
+                "login":"twitchdev",
+                "display_name":"TwitchDev",
+                "type":"",
+                "broadcaster_type":"partner",
+                "description":"Supporting third-party developers building Twitch integrations.",
+                "profile_image_url":"https://static-cdn.jtvnw.net/jtv_user_pictures/profile.png",
+                "offline_image_url":"https://static-cdn.jtvnw.net/jtv_user_pictures/offline.png",
+                "view_count":5980557,
+                "email":"not-real@email.com",
+                "created_at":"2016-12-14T20:32:28Z"
+            }]}"#,
+        )
+        .unwrap();
+        let user = &r.data[0];
+        assert_eq!(user.login, "twitchdev");
+        assert_eq!(user.user_type, UserType::Normal);
+        assert_eq!(user.broadcaster_type, BroadcasterType::Partner);
+        assert_eq!(user.created_at.timestamp(), 1481747548);
+        assert_eq!(user.email.as_deref(), Some("not-real@email.com"));
+    }
+    #[test]
+    fn empty_update() {
+        // Update User answering without the user must not panic
+        let r: Data<User> = serde_json::from_str(r#"{"data":[]}"#).unwrap();
+        match first(r.data) {
+            Err(ApiError::EmptyResponse(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+extern crate chrono;
+
+use self::chrono::prelude::*;
+
+use serde::de::{self, Deserialize, Deserializer};
+
+use super::super::auth::Scope;
+use super::super::response::{ApiError, TwitchResult};
+use super::super::TwitchClient;
+use super::{check_len, image_url, url, Data, HelixIterator, MAX_IDS};
+
+use std::fmt;
+use std::time::Duration;
+
+/// At most this many videos can be deleted per request
+pub const MAX_DELETE: usize = 5;
+
+/// Gets videos by ID, or the videos of a user or game
+///
+/// `filter` only applies to user and game queries, and
+/// `language` only to game queries. Filters that don't apply
+/// to the query are rejected.
+///
+/// #### Authentication: `None`
+///
+pub fn get<'c>(
+    c: &'c TwitchClient,
+    query: &VideoQuery,
+    filter: &VideoFilter,
+) -> TwitchResult<HelixIterator<'c, Video>> {
+    let mut params: Vec<(&str, &str)> = Vec::new();
+    match *query {
+        VideoQuery::Ids(ref ids) => {
+            if filter.language.is_some()
+                || filter.period.is_some()
+                || filter.sort.is_some()
+                || filter.video_type.is_some()
+            {
+                return Err(ApiError::invalid_request(
+                    "Filters only apply to user and game queries",
+                ));
+            }
+            r#try!(check_len("id", ids, MAX_IDS));
+            if ids.is_empty() {
+                return Err(ApiError::invalid_request("No video IDs given"));
+            }
+            params.extend(ids.iter().map(|id| ("id", id.as_str())));
+            return Ok(HelixIterator::new(c, url("/videos", &params)));
+        }
+        VideoQuery::User(ref id) => {
+            if filter.language.is_some() {
+                return Err(ApiError::invalid_request(
+                    "The language filter only applies to game queries",
+                ));
+            }
+            params.push(("user_id", id));
+        }
+        VideoQuery::Game(ref id) => params.push(("game_id", id)),
+    }
+
+    let period = filter.period.map(|p| p.to_string());
+    let sort = filter.sort.map(|s| s.to_string());
+    let video_type = filter.video_type.map(|t| t.to_string());
+    if let Some(ref language) = filter.language {
+        params.push(("language", language));
+    }
+    if let Some(ref period) = period {
+        params.push(("period", period));
+    }
+    if let Some(ref sort) = sort {
+        params.push(("sort", sort));
+    }
+    if let Some(ref video_type) = video_type {
+        params.push(("type", video_type));
+    }
+    Ok(HelixIterator::new(c, url("/videos", &params)))
+}
+
+/// Deletes up to 5 videos and returns the IDs of the deleted ones
+///
+/// #### Authentication: `channel:manage:videos`
+///
+pub fn delete(c: &TwitchClient, ids: &[&str]) -> TwitchResult<Vec<String>> {
+    r#try!(check_len("id", ids, MAX_DELETE));
+    if ids.is_empty() {
+        return Err(ApiError::invalid_request("No video IDs given"));
+    }
+    r#try!(c.require_scopes(&[Scope::ChannelManageVideos]));
+    let params: Vec<(&str, &str)> = ids.iter().map(|id| ("id", *id)).collect();
+    let r = r#try!(c.helix_delete::<Data<String>>(&url("/videos", &params)));
+    Ok(r.data)
+}
+
+///////////////////////////////////////
+// VideoQuery
+///////////////////////////////////////
+#[derive(Debug, Clone)]
+pub enum VideoQuery {
+    /// Up to 100 video IDs
+    Ids(Vec<String>),
+    /// The videos of a user
+    User(String),
+    /// The videos of a game
+    Game(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VideoFilter {
+    pub language: Option<String>,
+    pub period: Option<VideoPeriod>,
+    pub sort: Option<VideoSort>,
+    pub video_type: Option<VideoType>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VideoPeriod {
+    All,
+    Day,
+    Month,
+    Week,
+}
+
+impl fmt::Display for VideoPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VideoPeriod::All => write!(f, "all"),
+            VideoPeriod::Day => write!(f, "day"),
+            VideoPeriod::Month => write!(f, "month"),
+            VideoPeriod::Week => write!(f, "week"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VideoSort {
+    Time,
+    Trending,
+    Views,
+}
+
+impl fmt::Display for VideoSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VideoSort::Time => write!(f, "time"),
+            VideoSort::Trending => write!(f, "trending"),
+            VideoSort::Views => write!(f, "views"),
+        }
+    }
+}
+
+/// The kind of a video; `All` is only meaningful as a filter
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum VideoType {
+    #[serde(rename = "all")]
+    All,
+    #[serde(rename = "archive")]
+    Archive,
+    #[serde(rename = "highlight")]
+    Highlight,
+    #[serde(rename = "upload")]
+    Upload,
+}
+
+impl fmt::Display for VideoType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VideoType::All => write!(f, "all"),
+            VideoType::Archive => write!(f, "archive"),
+            VideoType::Highlight => write!(f, "highlight"),
+            VideoType::Upload => write!(f, "upload"),
+        }
+    }
+}
+
+///////////////////////////////////////
+// Video
+///////////////////////////////////////
+#[derive(Deserialize, Debug, Clone)]
+pub struct Video {
+    pub id: String,
+    /// The stream an archived video was recorded from
+    pub stream_id: Option<String>,
+    pub user_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    pub title: String,
+    pub description: String,
+    pub created_at: DateTime<UTC>,
+    pub published_at: DateTime<UTC>,
+    pub url: String,
+    /// Contains `%{width}` and `%{height}` placeholders,
+    /// see `thumbnail`
+    pub thumbnail_url: String,
+    pub viewable: String,
+    pub view_count: i64,
+    pub language: String,
+    #[serde(rename = "type")]
+    pub video_type: VideoType,
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub duration: Duration,
+    pub muted_segments: Option<Vec<MutedSegment>>,
+}
+
+impl Video {
+    /// The thumbnail URL for the given size
+    pub fn thumbnail(&self, width: u32, height: u32) -> String {
+        image_url(&self.thumbnail_url, width, height)
+    }
+}
+
+/// A muted part of a video, in seconds
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct MutedSegment {
+    pub duration: u64,
+    pub offset: u64,
+}
+
+/// Parses durations like `3h8m33s`
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration {}", s);
+    let mut secs: u64 = 0;
+    let mut num = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            num.push(c);
+            continue;
+        }
+        let n: u64 = r#try!(num.parse().map_err(|_| invalid()));
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        secs = r#try!(n
+            .checked_mul(unit)
+            .and_then(|n| n.checked_add(secs))
+            .ok_or_else(invalid));
+        num.clear();
+    }
+    if !num.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+fn deserialize_duration<D: Deserializer>(d: D) -> Result<Duration, D::Error> {
+    let s = r#try!(String::deserialize(d));
+    parse_duration(&s).map_err(de::Error::custom)
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use std::time::Duration;
+
+    use super::super::super::new;
+    use super::super::super::response::ApiError;
+    use super::super::Data;
+    use super::{MutedSegment, Video, VideoFilter, VideoQuery, VideoSort, VideoType};
+
+    #[test]
+    fn video() {
+        let r: Data<Video> = serde_json::from_str(
+            r#"{"data":[{
+                "id":"335921245",
+                "stream_id":null,
+                "user_id":"141981764",
+                "user_login":"twitchdev",
+                "user_name":"TwitchDev",
+                "title":"Twitch Developers 101",
+                "description":"Welcome to Twitch development!",
+                "created_at":"2018-11-14T21:30:18Z",
+                "published_at":"2018-11-14T22:04:30Z",
+                "url":"https://www.twitch.tv/videos/335921245",
+                "thumbnail_url":"https://static-cdn.jtvnw.net/cf_vods/d2nvs31859zcd8/twitchdev/335921245/ce0f3a7f-57a3-4152-bc06-0c6610189fb3/thumb/index-0000000000-%{width}x%{height}.jpg",
+                "viewable":"public",
+                "view_count":1863062,
+                "language":"en",
+                "type":"upload",
+                "duration":"3m21s",
+                "muted_segments":[{"duration":30,"offset":120}]
+            }],"pagination":{}}"#,
+        )
+        .unwrap();
+        let video = &r.data[0];
+        assert_eq!(video.video_type, VideoType::Upload);
+        assert_eq!(video.duration, Duration::from_secs(201));
+        assert_eq!(
+            video.muted_segments,
+            Some(vec![MutedSegment {
+                duration: 30,
+                offset: 120,
+            }])
+        );
+        assert!(video
+            .thumbnail(320, 180)
+            .ends_with("index-0000000000-320x180.jpg"));
+    }
+
+    #[test]
+    fn parse_duration() {
+        assert_eq!(
+            super::parse_duration("3h8m33s"),
+            Ok(Duration::from_secs(11313))
+        );
+        assert_eq!(super::parse_duration("45s"), Ok(Duration::from_secs(45)));
+        assert!(super::parse_duration("3h8").is_err());
+        assert!(super::parse_duration("3d").is_err());
+        assert!(super::parse_duration("18446744073709551615h").is_err());
+        assert!(super::parse_duration("18446744073709551615s1s").is_err());
+    }
+
+    #[test]
+    fn limits() {
+        let c = new(String::from("cid"));
+        match super::delete(&c, &["1", "2", "3", "4", "5", "6"]) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r),
+        }
+        match super::get(&c, &VideoQuery::Ids(vec![]), &VideoFilter::default()) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+        let filter = VideoFilter {
+            language: Some(String::from("en")),
+            ..VideoFilter::default()
+        };
+        match super::get(&c, &VideoQuery::User(String::from("1")), &filter) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+        match super::get(&c, &VideoQuery::Ids(vec![String::from("1")]), &filter) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+        assert!(super::get(&c, &VideoQuery::Game(String::from("1")), &filter).is_ok());
+
+        let filter = VideoFilter {
+            sort: Some(VideoSort::Views),
+            ..VideoFilter::default()
+        };
+        match super::get(&c, &VideoQuery::Ids(vec![String::from("1")]), &filter) {
+            Err(ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+        assert!(super::get(&c, &VideoQuery::User(String::from("1")), &filter).is_ok());
+    }
+}
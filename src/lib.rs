@@ -43,46 +43,98 @@ extern crate hyper;
 extern crate hyper_rustls;
 extern crate serde;
 extern crate serde_json;
+extern crate urlparse;
 
 #[macro_use]
 pub mod response;
+pub mod auth;
 pub mod channel_feed;
 pub mod channels;
 pub mod chat;
 pub mod communities;
 pub mod games;
+pub mod helix;
 pub mod ingests;
+mod ratelimit;
 pub mod search;
 pub mod streams;
 pub mod teams;
 pub mod users;
 pub mod videos;
 
-use response::{ApiError, ErrorResponse, TwitchResult};
+use auth::{AccessToken, Scope, SerdeValidatedToken, TokenKey, TokenStore, ValidatedToken};
+use ratelimit::RateLimiter;
+use response::{ApiError, AuthError, ErrorResponse, MissingScope, TwitchResult};
 
-use hyper::client::RequestBuilder;
+pub use ratelimit::RateLimit;
+
+use hyper::client::{RequestBuilder, Response};
 use hyper::header::{qitem, Accept, Authorization, ContentType, Headers};
 use hyper::mime::{Attr, Mime, SubLevel, TopLevel, Value};
 use hyper::net::HttpsConnector;
+use hyper::status::StatusCode;
 use hyper::Client;
 
 use serde::de::Deserialize;
 use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::io::{stderr, Read};
+use std::sync::{Arc, Mutex};
+use urlparse::quote_plus;
 
-#[derive(Debug)]
+/// A Twitch API client
+///
+/// Cloning a client, or deriving one with `with_token`, is cheap:
+/// all handles share one connection pool and rate limiter.
+/// Clones also share their token, so a refresh through an
+/// attached token store applies to all of them.
+#[derive(Debug, Clone)]
 pub struct TwitchClient {
-    client: Client,
+    shared: Arc<Shared>,
     cid: String,
-    token: Option<String>,
+    token: Arc<Mutex<Option<String>>>,
+    validated: Option<ValidatedToken>,
+    store: Option<Arc<AttachedStore>>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    client: Client,
+    limiter: RateLimiter,
+}
+
+/// The token store a client refreshes its token through
+struct AttachedStore {
+    store: Arc<dyn TokenStore + Send + Sync>,
+    key: TokenKey,
+    client_secret: String,
+    /// Held while refreshing, so concurrent requests that get
+    /// rejected refresh the token only once
+    refreshing: Mutex<()>,
+}
+
+impl fmt::Debug for AttachedStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AttachedStore")
+            .field("key", &self.key)
+            .field("client_secret", &"<redacted>")
+            .finish()
+    }
 }
 
 pub fn new(clientid: String) -> TwitchClient {
     TwitchClient {
-        client: Client::with_connector(HttpsConnector::new(hyper_rustls::TlsClient::new())),
+        shared: Arc::new(Shared {
+            client: Client::with_connector(HttpsConnector::new(hyper_rustls::TlsClient::new())),
+            limiter: RateLimiter::new(),
+        }),
         cid: clientid.clone(),
-        token: None,
+        token: Arc::new(Mutex::new(None)),
+        validated: None,
+        store: None,
     }
 }
 
@@ -105,64 +157,208 @@ impl TwitchClient {
             SubLevel::Json,
             vec![(Attr::Charset, Value::Utf8)],
         )));
-        if let Some(ref token) = self.token {
+        if let Some(token) = self.token() {
             headers.set(Authorization(format!("OAuth {}", token)));
         }
 
         build(&url).headers(headers)
     }
 
-    pub fn set_oauth_token(&mut self, token: &str) {
-        self.token = Some(String::from(token));
+    /// Returns a handle that sends requests with `token`
+    ///
+    /// Meant for acting on behalf of many users at once without
+    /// touching the token of `self`:
+    ///
+    /// ```ignore
+    /// let user = c.with_token(&tok);
+    /// let channel = channels::get(&user)?;
+    /// ```
+    ///
+    pub fn with_token(&self, token: &str) -> TwitchClient {
+        let validated = match self.token() {
+            Some(ref t) if t == token => self.validated.clone(),
+            _ => None,
+        };
+        TwitchClient {
+            shared: self.shared.clone(),
+            cid: self.cid.clone(),
+            token: Arc::new(Mutex::new(Some(String::from(token)))),
+            validated: validated,
+            store: None,
+        }
     }
 
-    pub fn get<T: Deserialize>(&self, path: &str) -> TwitchResult<T> {
-        let mut r = r#try!(self.build_request(path, |url| self.client.get(url)).send());
-        let mut s = String::new();
-        let _ = r#try!(r.read_to_string(&mut s));
-        if s.len() == 0 {
-            return Err(ApiError::empty_response());
-        } else {
-            match serde_json::from_str(&s) {
-                Ok(x) => Ok(x),
-                Err(err) => {
-                    if let Ok(mut e) = serde_json::from_str::<ErrorResponse>(&s) {
-                        e.cause = Some(Box::new(err));
-                        return Err(ApiError::from(e));
-                    }
-                    writeln!(&mut stderr(), "Serde Parse Fail:\n\"{}\"", &s).unwrap();
-                    Err(ApiError::from(err))
-                }
+    /// The request budget Twitch reported with the last response
+    /// to a handle using the same token
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.shared.limiter.get(&self.limit_key())
+    }
+
+    /// The rate limit bucket of the current token, requests
+    /// without one share the client ID's bucket
+    ///
+    /// Tokens are hashed, so the limiter holds no secrets.
+    ///
+    fn limit_key(&self) -> String {
+        match self.token() {
+            Some(token) => {
+                let mut h = DefaultHasher::new();
+                token.hash(&mut h);
+                format!("token:{:016x}", h.finish())
             }
+            None => format!("client:{}", self.cid),
         }
     }
 
-    pub fn post<T, R>(&self, path: &str, data: &T) -> TwitchResult<R>
+    pub fn set_oauth_token(&mut self, token: &str) {
+        self.shared.limiter.remove(&self.limit_key());
+        self.token = Arc::new(Mutex::new(Some(String::from(token))));
+        self.validated = None;
+    }
+
+    fn token(&self) -> Option<String> {
+        self.token.lock().unwrap().clone()
+    }
+
+    /// Keeps the token of this handle in `store` under `key`
+    ///
+    /// A token already stored under `key` becomes the current
+    /// token. Whenever Helix rejects the token with
+    /// `401 Unauthorized`, it is refreshed with `client_secret`,
+    /// the new pair is written back to the store and the request
+    /// is sent once more.
+    ///
+    pub fn set_token_store<S>(
+        &mut self,
+        store: Arc<S>,
+        key: TokenKey,
+        client_secret: &str,
+    ) -> TwitchResult<()>
     where
-        T: Serialize,
-        R: Deserialize,
+        S: TokenStore + Send + Sync + 'static,
     {
+        if let Some(t) = r#try!(store.load(&key)) {
+            self.set_oauth_token(&t.access_token);
+        }
+        self.store = Some(Arc::new(AttachedStore {
+            store: store,
+            key: key,
+            client_secret: String::from(client_secret),
+            refreshing: Mutex::new(()),
+        }));
+        Ok(())
+    }
+
+    /// Refreshes the token through the attached token store and
+    /// writes the new pair back to it
+    pub fn refresh_token(&self) -> TwitchResult<AccessToken> {
+        let s = match self.store {
+            Some(ref s) => s,
+            None => {
+                return Err(ApiError::from(AuthError {
+                    error: "missing_token_store".to_owned(),
+                    description: "No token store has been attached".to_owned(),
+                }))
+            }
+        };
+        let _guard = s.refreshing.lock().unwrap();
+        self.refresh_with(s)
+    }
+
+    fn refresh_with(&self, s: &AttachedStore) -> TwitchResult<AccessToken> {
+        let new = r#try!(auth::refresh_in_store(
+            self,
+            &s.client_secret,
+            &*s.store,
+            &s.key
+        ));
+        self.shared.limiter.remove(&self.limit_key());
+        *self.token.lock().unwrap() = Some(new.access_token.clone());
+        Ok(new)
+    }
+
+    /// Refreshes the token after Twitch rejected `rejected`,
+    /// unless another request refreshed it in the meantime
+    ///
+    /// Returns whether the request should be sent again.
+    ///
+    fn refresh_rejected(&self, rejected: &Option<String>) -> TwitchResult<bool> {
+        let s = match self.store {
+            Some(ref s) => s,
+            None => return Ok(false),
+        };
+        let _guard = s.refreshing.lock().unwrap();
+        if self.token() == *rejected {
+            r#try!(self.refresh_with(s));
+        }
+        Ok(true)
+    }
+
+    /// Validates the OAuth token with Twitch and remembers the result
+    ///
+    /// Once a token has been validated, endpoints check their
+    /// required scopes against it and fail with
+    /// `ApiError::MissingScope` without sending a request.
+    ///
+    pub fn validate_token(&mut self) -> TwitchResult<ValidatedToken> {
+        let token = match self.token() {
+            Some(token) => token,
+            None => {
+                return Err(ApiError::from(AuthError {
+                    error: "missing_token".to_owned(),
+                    description: "No OAuth token has been set".to_owned(),
+                }))
+            }
+        };
+        let mut headers = Headers::new();
+        headers.set(Authorization(format!("OAuth {}", token)));
+
         let mut r = r#try!(self
-            .build_request(path, |url| self.client.post(url))
-            .body(&r#try!(serde_json::to_string(data)))
+            .shared
+            .client
+            .get("https://id.twitch.tv/oauth2/validate")
+            .headers(headers)
             .send());
-        let mut s = String::new();
-        let _ = r#try!(r.read_to_string(&mut s));
-        if s.len() == 0 {
-            return Err(ApiError::empty_response());
-        } else {
-            match serde_json::from_str(&s) {
-                Ok(x) => Ok(x),
-                Err(err) => {
-                    if let Ok(mut e) = serde_json::from_str::<ErrorResponse>(&s) {
-                        e.cause = Some(Box::new(err));
-                        return Err(ApiError::from(e));
-                    }
-                    writeln!(&mut stderr(), "Serde Parse Fail:\n\"{}\"", &s).unwrap();
-                    Err(ApiError::from(err))
-                }
+        let v = ValidatedToken::from(r#try!(read_response::<SerdeValidatedToken>(&mut r)));
+        self.validated = Some(v.clone());
+        Ok(v)
+    }
+
+    /// The result of the last `validate_token` call for the current token
+    pub fn token_info(&self) -> Option<&ValidatedToken> {
+        self.validated.as_ref()
+    }
+
+    /// Fails with `ApiError::MissingScope` if the validated
+    /// token lacks any of `scopes`
+    ///
+    /// Does nothing while the token has not been validated.
+    ///
+    pub fn require_scopes(&self, scopes: &[Scope]) -> TwitchResult<()> {
+        if let Some(ref v) = self.validated {
+            let missing = v.scopes.missing(scopes);
+            if !missing.is_empty() {
+                return Err(ApiError::from(MissingScope { missing: missing }));
             }
         }
+        Ok(())
+    }
+
+    pub fn get<T: Deserialize>(&self, path: &str) -> TwitchResult<T> {
+        let mut r = r#try!(self.send(self.build_request(path, |url| self.shared.client.get(url))));
+        read_response(&mut r)
+    }
+
+    pub fn post<T, R>(&self, path: &str, data: &T) -> TwitchResult<R>
+    where
+        T: Serialize,
+        R: Deserialize,
+    {
+        let mut r = r#try!(self.send(
+            self.build_request(path, |url| self.shared.client.post(url))
+                .body(&r#try!(serde_json::to_string(data)))
+        ));
+        read_response(&mut r)
     }
 
     pub fn put<T, R>(&self, path: &str, data: &T) -> TwitchResult<R>
@@ -170,138 +366,377 @@ impl TwitchClient {
         T: Serialize,
         R: Deserialize,
     {
+        let mut r = r#try!(self.send(
+            self.build_request(path, |url| self.shared.client.put(url))
+                .body(&r#try!(serde_json::to_string(data)))
+        ));
+        read_response(&mut r)
+    }
+
+    /// Sends a plain GET to an absolute URL outside the Kraken API
+    ///
+    /// These are `id.twitch.tv` URLs, which don't count against
+    /// the API rate limit.
+    ///
+    fn get_url<T: Deserialize>(&self, url: &str) -> TwitchResult<T> {
+        let mut r = r#try!(self.shared.client.get(url).send());
+        read_response(&mut r)
+    }
+
+    /// Sends a form-encoded POST to an absolute URL, as used by
+    /// the `id.twitch.tv` OAuth endpoints
+    fn post_form<R: Deserialize>(&self, url: &str, params: &[(&str, &str)]) -> TwitchResult<R> {
+        let mut headers = Headers::new();
+        headers.set(ContentType::form_url_encoded());
+
         let mut r = r#try!(self
-            .build_request(path, |url| self.client.put(url))
-            .body(&r#try!(serde_json::to_string(data)))
+            .shared
+            .client
+            .post(url)
+            .headers(headers)
+            .body(&form_encode(params))
             .send());
-        let mut s = String::new();
-        let _ = r#try!(r.read_to_string(&mut s));
-        if s.len() == 0 {
-            return Err(ApiError::empty_response());
-        } else {
-            match serde_json::from_str(&s) {
-                Ok(x) => Ok(x),
-                Err(err) => {
-                    if let Ok(mut e) = serde_json::from_str::<ErrorResponse>(&s) {
-                        e.cause = Some(Box::new(err));
-                        return Err(ApiError::from(e));
-                    }
-                    writeln!(&mut stderr(), "Serde Parse Fail:\n\"{}\"", &s).unwrap();
-                    Err(ApiError::from(err))
-                }
-            }
-        }
+        read_response(&mut r)
     }
 
     pub fn delete<T: Deserialize>(&self, path: &str) -> TwitchResult<T> {
-        let mut r = r#try!(self
-            .build_request(path, |url| self.client.delete(url))
-            .send());
-        let mut s = String::new();
-        let _ = r#try!(r.read_to_string(&mut s));
-        if s.len() == 0 {
-            return Err(ApiError::empty_response());
-        } else {
-            match serde_json::from_str(&s) {
-                Ok(x) => Ok(x),
-                Err(err) => {
-                    if let Ok(mut e) = serde_json::from_str::<ErrorResponse>(&s) {
-                        e.cause = Some(Box::new(err));
-                        return Err(ApiError::from(e));
-                    }
-                    writeln!(&mut stderr(), "Serde Parse Fail:\n\"{}\"", &s).unwrap();
-                    Err(ApiError::from(err))
+        let mut r =
+            r#try!(self.send(self.build_request(path, |url| self.shared.client.delete(url))));
+        read_response(&mut r)
+    }
+
+    fn build_helix_request<'a, F>(&self, path: &str, build: F) -> RequestBuilder<'a>
+    where
+        F: Fn(&str) -> RequestBuilder<'a>,
+    {
+        let url = String::from("https://api.twitch.tv/helix") + path;
+        let mut headers = Headers::new();
+
+        headers.set_raw("Client-Id", vec![self.cid.clone().into_bytes()]);
+        headers.set(ContentType(Mime(
+            TopLevel::Application,
+            SubLevel::Json,
+            vec![(Attr::Charset, Value::Utf8)],
+        )));
+        if let Some(token) = self.token() {
+            headers.set(Authorization(format!("Bearer {}", token)));
+        }
+
+        build(&url).headers(headers)
+    }
+
+    /// Sends a GET to the Helix API
+    ///
+    /// `path` is relative to `https://api.twitch.tv/helix` and
+    /// usually deserializes into a `helix::Data<T>`.
+    ///
+    pub fn helix_get<T: Deserialize>(&self, path: &str) -> TwitchResult<T> {
+        let mut r = r#try!(self.helix_send(path, "null", |url| self.shared.client.get(url)));
+        read_helix_response(&mut r)
+    }
+
+    /// Sends a POST to the Helix API
+    ///
+    /// `data` is sent as the JSON body unless it serializes to
+    /// `null`, so pass `&()` for endpoints that only take query
+    /// parameters. Use `R = ()` for endpoints answering with
+    /// `204 No Content`.
+    ///
+    pub fn helix_post<T, R>(&self, path: &str, data: &T) -> TwitchResult<R>
+    where
+        T: Serialize,
+        R: Deserialize,
+    {
+        let body = r#try!(serde_json::to_string(data));
+        let mut r = r#try!(self.helix_send(path, &body, |url| self.shared.client.post(url)));
+        read_helix_response(&mut r)
+    }
+
+    /// Sends a PATCH to the Helix API, see `helix_post`
+    pub fn helix_patch<T, R>(&self, path: &str, data: &T) -> TwitchResult<R>
+    where
+        T: Serialize,
+        R: Deserialize,
+    {
+        let body = r#try!(serde_json::to_string(data));
+        let mut r = r#try!(self.helix_send(path, &body, |url| self.shared.client.patch(url)));
+        read_helix_response(&mut r)
+    }
+
+    /// Sends a PUT to the Helix API, see `helix_post`
+    pub fn helix_put<T, R>(&self, path: &str, data: &T) -> TwitchResult<R>
+    where
+        T: Serialize,
+        R: Deserialize,
+    {
+        let body = r#try!(serde_json::to_string(data));
+        let mut r = r#try!(self.helix_send(path, &body, |url| self.shared.client.put(url)));
+        read_helix_response(&mut r)
+    }
+
+    /// Sends a DELETE to the Helix API, see `helix_post`
+    pub fn helix_delete<R: Deserialize>(&self, path: &str) -> TwitchResult<R> {
+        let mut r = r#try!(self.helix_send(path, "null", |url| self.shared.client.delete(url)));
+        read_helix_response(&mut r)
+    }
+
+    /// Sends a Helix request, and once more after refreshing the
+    /// token if Twitch rejects it and a token store is attached
+    fn helix_send<'a, F>(&'a self, path: &str, body: &'a str, build: F) -> TwitchResult<Response>
+    where
+        F: Fn(&str) -> RequestBuilder<'a>,
+    {
+        let token = self.token();
+        let r = r#try!(self.send(with_body(self.build_helix_request(path, &build), body)));
+        if r.status == StatusCode::Unauthorized && r#try!(self.refresh_rejected(&token)) {
+            return self.send(with_body(self.build_helix_request(path, &build), body));
+        }
+        Ok(r)
+    }
+
+    /// Sends an API request once the shared rate limiter allows it
+    fn send(&self, request: RequestBuilder) -> TwitchResult<Response> {
+        let key = self.limit_key();
+        self.shared.limiter.acquire(&key);
+        let r = r#try!(request.send());
+        self.shared.limiter.update(&key, &r.headers);
+        Ok(r)
+    }
+}
+
+fn read_response<T: Deserialize>(r: &mut Response) -> TwitchResult<T> {
+    let mut s = String::new();
+    let _ = r#try!(r.read_to_string(&mut s));
+    if s.len() == 0 {
+        return Err(ApiError::empty_response());
+    } else {
+        match serde_json::from_str(&s) {
+            Ok(x) => Ok(x),
+            Err(err) => {
+                if let Ok(mut e) = serde_json::from_str::<ErrorResponse>(&s) {
+                    e.cause = Some(Box::new(err));
+                    return Err(ApiError::from(e));
                 }
+                writeln!(&mut stderr(), "Serde Parse Fail:\n\"{}\"", &s).unwrap();
+                Err(ApiError::from(err))
             }
         }
     }
 }
 
-pub mod auth {
-    use std::fmt;
-
-    use super::TwitchClient;
-
-    #[derive(Debug)]
-    #[allow(non_camel_case_types)]
-    pub enum Scope {
-        channel_check_subscription,
-        channel_commercial,
-        channel_editor,
-        channel_feed_edit,
-        channel_feed_read,
-        channel_read,
-        channel_stream,
-        channel_subscriptions,
-        chat_login,
-        user_blocks_edit,
-        user_blocks_read,
-        user_follows_edit,
-        user_read,
-        user_subscriptions,
-        viewing_activity_ready,
-    }
-
-    impl fmt::Display for Scope {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            fmt::Debug::fmt(self, f)
-        }
+/// Like `read_response`, but Helix reports errors through the
+/// status code and answers some requests with an empty body
+fn read_helix_response<T: Deserialize>(r: &mut Response) -> TwitchResult<T> {
+    let mut s = String::new();
+    let _ = r#try!(r.read_to_string(&mut s));
+    if !r.status.is_success() {
+        return Err(ApiError::from_helix(
+            match serde_json::from_str::<ErrorResponse>(&s) {
+                Ok(e) => e,
+                Err(_) => ErrorResponse {
+                    error: r.status.canonical_reason().unwrap_or("").to_owned(),
+                    status: r.status.to_u16() as i32,
+                    message: s,
+                    cause: None,
+                },
+            },
+        ));
     }
+    parse_helix_body(&s)
+}
 
-    // TODO: replace with:
-    // https://doc.rust-lang.org/std/slice/trait.SliceConcatExt.html
-    fn format_scope(scopes: &[Scope]) -> String {
-        let mut res = String::with_capacity(27 * scopes.len());
-        for scope in scopes.iter() {
-            res.push_str(&scope.to_string());
-            res.push('+');
+fn parse_helix_body<T: Deserialize>(s: &str) -> TwitchResult<T> {
+    if s.trim().is_empty() {
+        // `()` and `Option<_>` accept a missing body
+        return serde_json::from_str("null").map_err(|_| ApiError::empty_response());
+    }
+    match serde_json::from_str(s) {
+        Ok(x) => Ok(x),
+        Err(err) => {
+            writeln!(&mut stderr(), "Serde Parse Fail:\n\"{}\"", s).unwrap();
+            Err(ApiError::from(err))
         }
-        res.trim_end_matches('+').to_owned()
-    }
-
-    fn gen_auth_url(
-        c: &TwitchClient,
-        rtype: &str,
-        redirect_url: &str,
-        scope: &[Scope],
-        state: &str,
-    ) -> String {
-        String::from("https://api.twitch.tv/kraken/oauth2/authorize")
-            + "?response_type="
-            + rtype
-            + "&client_id="
-            + &c.cid
-            + "&redirect_uri="
-            + redirect_url
-            + "&scope="
-            + &format_scope(scope)
-            + "&state="
-            + state
-    }
-
-    pub fn auth_code_flow(
-        c: &TwitchClient,
-        redirect_url: &str,
-        scope: &[Scope],
-        state: &str,
-    ) -> String {
-        gen_auth_url(c, "code", redirect_url, scope, state)
-    }
-
-    pub fn imp_grant_flow(
-        c: &TwitchClient,
-        redirect_url: &str,
-        scope: &[Scope],
-        state: &str,
-    ) -> String {
-        gen_auth_url(c, "token", redirect_url, scope, state)
     }
 }
 
+fn with_body<'a>(request: RequestBuilder<'a>, body: &'a str) -> RequestBuilder<'a> {
+    if body == "null" {
+        request
+    } else {
+        request.body(body)
+    }
+}
+
+fn form_encode(params: &[(&str, &str)]) -> String {
+    params
+        .iter()
+        .map(|&(k, v)| format!("{}={}", k, quote_plus(v, b"").unwrap()))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
 #[cfg(test)]
 mod tests {
     pub const CLIENTID: &'static str = "";
     pub const TOKEN: &'static str = "";
     pub const CHANID: &'static str = "";
     pub const TESTCH: i64 = 12826;
+
+    use super::auth::{
+        AccessToken, MemoryTokenStore, Scope, ScopeSet, TokenKey, TokenStore, ValidatedToken,
+    };
+    use super::helix::Data;
+    use super::response::ApiError;
+    use hyper::header::Headers;
+    use std::sync::Arc;
+
+    #[test]
+    fn with_token() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<super::TwitchClient>();
+
+        let mut c = super::new(String::from(CLIENTID));
+        c.set_oauth_token("a");
+        c.validated = Some(ValidatedToken {
+            client_id: String::from(CLIENTID),
+            login: None,
+            user_id: None,
+            scopes: ScopeSet::new(),
+            expires_in: 3600,
+        });
+
+        let a = c.with_token("a");
+        let b = c.with_token("b");
+        assert!(Arc::ptr_eq(&a.shared, &c.shared));
+        assert!(Arc::ptr_eq(&b.shared, &c.shared));
+        assert!(a.token_info().is_some());
+        assert_eq!(b.token().as_deref(), Some("b"));
+        assert!(b.token_info().is_none());
+        assert_eq!(c.token().as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn limit_key() {
+        let mut c = super::new(String::from("cid"));
+        assert_eq!(c.limit_key(), "client:cid");
+
+        c.set_oauth_token("secret");
+        let key = c.limit_key();
+        assert!(key.starts_with("token:"));
+        assert!(!key.contains("secret"));
+        assert_eq!(c.with_token("secret").limit_key(), key);
+        assert_ne!(c.with_token("other").limit_key(), key);
+
+        let mut h = Headers::new();
+        h.set_raw("Ratelimit-Limit", vec![b"800".to_vec()]);
+        h.set_raw("Ratelimit-Remaining", vec![b"799".to_vec()]);
+        h.set_raw("Ratelimit-Reset", vec![b"0".to_vec()]);
+        c.shared.limiter.update(&key, &h);
+        assert!(c.rate_limit().is_some());
+        c.set_oauth_token("rotated");
+        assert!(c.rate_limit().is_none());
+        assert!(c.shared.limiter.get(&key).is_none());
+    }
+
+    #[test]
+    fn token_store() {
+        let mut c = super::new(String::from(CLIENTID));
+        match c.refresh_token() {
+            Err(ApiError::AuthError(e)) => assert_eq!(e.error, "missing_token_store"),
+            r => panic!("{:?}", r),
+        }
+
+        let store = Arc::new(MemoryTokenStore::new());
+        let key = TokenKey::User(String::from("1234"));
+        store
+            .save(
+                &key,
+                &AccessToken {
+                    access_token: String::from("stored"),
+                    refresh_token: None,
+                    expires_in: Some(3600),
+                    scope: vec![],
+                    token_type: String::from("bearer"),
+                    id_token: None,
+                },
+            )
+            .unwrap();
+        c.set_token_store(store.clone(), key, "s3cr3t").unwrap();
+        assert_eq!(c.token().as_deref(), Some("stored"));
+        assert!(!format!("{:?}", c).contains("s3cr3t"));
+
+        // without a refresh token nothing is sent
+        match c.refresh_rejected(&Some(String::from("stored"))) {
+            Err(ApiError::AuthError(e)) => assert_eq!(e.error, "missing_refresh_token"),
+            r => panic!("{:?}", r),
+        }
+        // another request refreshed already, just retry
+        assert!(c.refresh_rejected(&Some(String::from("old"))).unwrap());
+        assert!(!c.with_token("x").refresh_rejected(&None).unwrap());
+
+        // clones see a refreshed token
+        let d = c.clone();
+        *c.token.lock().unwrap() = Some(String::from("refreshed"));
+        assert_eq!(d.token().as_deref(), Some("refreshed"));
+    }
+
+    #[test]
+    fn parse_helix_body() {
+        assert!(super::parse_helix_body::<()>("").is_ok());
+        assert!(super::parse_helix_body::<Option<Data<i32>>>("")
+            .unwrap()
+            .is_none());
+        match super::parse_helix_body::<Data<i32>>("") {
+            Err(ApiError::EmptyResponse(_)) => (),
+            r => panic!("{:?}", r),
+        }
+        let d = super::parse_helix_body::<Data<i32>>(r#"{"data":[1]}"#).unwrap();
+        assert_eq!(d.data, vec![1]);
+    }
+
+    #[test]
+    fn helix_errors() {
+        let e = |s: &str| ApiError::from_helix(serde_json::from_str(s).unwrap());
+        match e(
+            r#"{"error":"Bad Request","status":400,"message":"The parameter \"status\" was malformed"}"#,
+        ) {
+            ApiError::BadRequest(e) => assert_eq!(e.error, "Bad Request"),
+            r => panic!("{:?}", r),
+        }
+        match e(
+            r#"{"error":"Forbidden","status":403,"message":"The user is not one of the broadcaster's moderators"}"#,
+        ) {
+            ApiError::Forbidden(_) => (),
+            r => panic!("{:?}", r),
+        }
+        match e(r#"{"error":"Not Found","status":404,"message":""}"#) {
+            ApiError::TwitchError(_) => (),
+            r => panic!("{:?}", r),
+        }
+        assert_eq!(e(r#"{"status":403,"message":""}"#).status(), Some(403));
+        assert_eq!(ApiError::timeout().status(), None);
+    }
+
+    #[test]
+    fn require_scopes() {
+        let mut c = super::new(String::from(CLIENTID));
+        c.set_oauth_token(TOKEN);
+        assert!(c.require_scopes(&[Scope::ChatRead]).is_ok());
+
+        c.validated = Some(ValidatedToken {
+            client_id: String::from(CLIENTID),
+            login: None,
+            user_id: None,
+            scopes: ScopeSet::from(&[Scope::ChatRead][..]),
+            expires_in: 3600,
+        });
+        assert!(c.require_scopes(&[Scope::ChatRead]).is_ok());
+        match c.require_scopes(&[Scope::ChatRead, Scope::ChatEdit]) {
+            Err(ApiError::MissingScope(e)) => assert_eq!(e.missing.to_vec(), vec![Scope::ChatEdit]),
+            r => panic!("{:?}", r),
+        }
+
+        c.set_oauth_token(TOKEN);
+        assert!(c.require_scopes(&[Scope::ChatEdit]).is_ok());
+    }
 }
@@ -0,0 +1,210 @@
+// This file was ((taken|adapted)|contains (data|code)) from twitch_api,
+// Copyright 2017 Matt Shanker
+// It's licensed under the Apache License, Version 2.0.
+// You may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// (Modifications|Other (data|code)|Everything else) Copyright 2019 the libtwitch-rs authors.
+//  See copying.md for further legal info.
+
+use hyper::header::Headers;
+
+use std::collections::HashMap;
+use std::str;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Twitch's view of the request budget, as reported by the
+/// `Ratelimit-*` headers of the last response
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Requests per window
+    pub limit: u32,
+    /// Requests left in the current window
+    pub remaining: u32,
+    /// Unix timestamp at which the window resets
+    pub reset: u64,
+}
+
+/// Shared by every handle of a `TwitchClient`
+///
+/// Twitch keeps a separate budget for every token, so each
+/// token gets its own bucket. Requests block once their
+/// bucket is used up until its window resets.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    buckets: Mutex<HashMap<String, RateLimit>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> RateLimiter {
+        RateLimiter {
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<RateLimit> {
+        self.buckets.lock().unwrap().get(key).cloned()
+    }
+
+    /// Waits until a request may be sent with the token `key`
+    /// and reserves it
+    pub fn acquire(&self, key: &str) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let l = match buckets.get_mut(key) {
+                    Some(l) => l,
+                    None => return,
+                };
+                if l.remaining > 0 {
+                    l.remaining -= 1;
+                    return;
+                }
+                let now = now();
+                if now >= l.reset {
+                    // the bucket is full again, the next response
+                    // reports the exact budget
+                    l.remaining = l.limit.saturating_sub(1);
+                    return;
+                }
+                // out of budget: the bucket stays empty, so every
+                // other caller waits for the reset as well
+                Duration::from_secs(l.reset - now)
+            };
+            thread::sleep(wait);
+        }
+    }
+
+    /// Forgets the bucket of a token that has been replaced
+    pub fn remove(&self, key: &str) {
+        self.buckets.lock().unwrap().remove(key);
+    }
+
+    /// Records the budget of the token `key` reported by a response
+    pub fn update(&self, key: &str, headers: &Headers) {
+        let limit = (
+            header(headers, "Ratelimit-Limit"),
+            header(headers, "Ratelimit-Remaining"),
+            header(headers, "Ratelimit-Reset"),
+        );
+        if let (Some(limit), Some(remaining), Some(reset)) = limit {
+            self.buckets.lock().unwrap().insert(
+                String::from(key),
+                RateLimit {
+                    limit: limit as u32,
+                    remaining: remaining as u32,
+                    reset: reset,
+                },
+            );
+        }
+    }
+}
+
+fn header(headers: &Headers, name: &str) -> Option<u64> {
+    headers
+        .get_raw(name)
+        .and_then(|v| v.first())
+        .and_then(|v| str::from_utf8(v).ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+///////////////////////////////////////
+// TESTS
+///////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::{now, RateLimit, RateLimiter};
+
+    fn headers(limit: u64, remaining: u64, reset: u64) -> Headers {
+        let mut h = Headers::new();
+        h.set_raw("Ratelimit-Limit", vec![limit.to_string().into_bytes()]);
+        h.set_raw(
+            "Ratelimit-Remaining",
+            vec![remaining.to_string().into_bytes()],
+        );
+        h.set_raw("Ratelimit-Reset", vec![reset.to_string().into_bytes()]);
+        h
+    }
+
+    #[test]
+    fn rate_limiter() {
+        let l = RateLimiter::new();
+        l.acquire("a");
+        assert_eq!(l.get("a"), None);
+
+        l.update("a", &Headers::new());
+        assert_eq!(l.get("a"), None);
+
+        let reset = now() + 60;
+        l.update("a", &headers(800, 2, reset));
+        l.acquire("a");
+        assert_eq!(
+            l.get("a"),
+            Some(RateLimit {
+                limit: 800,
+                remaining: 1,
+                reset: reset,
+            })
+        );
+        assert_eq!(l.get("b"), None);
+
+        // an exhausted budget whose window has passed does not block
+        l.update("a", &headers(800, 0, now() - 1));
+        let start = Instant::now();
+        l.acquire("a");
+        assert!(start.elapsed().as_secs() < 1);
+        assert_eq!(l.get("a").unwrap().remaining, 799);
+
+        l.remove("a");
+        assert_eq!(l.get("a"), None);
+    }
+
+    #[test]
+    fn exhausted_bucket() {
+        let l = Arc::new(RateLimiter::new());
+        l.update("a", &headers(800, 0, now() + 2));
+
+        // other tokens are not held up
+        let start = Instant::now();
+        l.acquire("b");
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        // every caller waits for the reset, not only the first
+        let waiters: Vec<_> = (0..2)
+            .map(|_| {
+                let l = l.clone();
+                thread::spawn(move || {
+                    let start = Instant::now();
+                    l.acquire("a");
+                    start.elapsed()
+                })
+            })
+            .collect();
+        for w in waiters {
+            assert!(w.join().unwrap() >= Duration::from_millis(900));
+        }
+    }
+}
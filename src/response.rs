@@ -22,15 +22,27 @@ use std::error::Error;
 use std::fmt;
 use std::io;
 
+use super::auth::ScopeSet;
+
 pub type TwitchResult<T> = Result<T, ApiError>;
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ApiError {
     HyperErr(hyper::error::Error),
     IoError(io::Error),
     ParseError(serde_json::error::Error),
     TwitchError(ErrorResponse),
+    /// A Helix request Twitch rejected as malformed (400)
+    BadRequest(ErrorResponse),
+    /// A Helix request the token's user may not make (403), like
+    /// moderating a channel they don't moderate
+    Forbidden(ErrorResponse),
     EmptyResponse(EmptyResponse),
+    Timeout(Timeout),
+    AuthError(AuthError),
+    MissingScope(MissingScope),
+    InvalidRequest(InvalidRequest),
 }
 
 impl From<hyper::error::Error> for ApiError {
@@ -57,10 +69,60 @@ impl From<ErrorResponse> for ApiError {
     }
 }
 
+impl From<AuthError> for ApiError {
+    fn from(err: AuthError) -> ApiError {
+        ApiError::AuthError(err)
+    }
+}
+
+impl From<MissingScope> for ApiError {
+    fn from(err: MissingScope) -> ApiError {
+        ApiError::MissingScope(err)
+    }
+}
+
+impl From<InvalidRequest> for ApiError {
+    fn from(err: InvalidRequest) -> ApiError {
+        ApiError::InvalidRequest(err)
+    }
+}
+
 impl ApiError {
     pub fn empty_response() -> ApiError {
         ApiError::EmptyResponse(EmptyResponse {})
     }
+
+    pub fn timeout() -> ApiError {
+        ApiError::Timeout(Timeout {})
+    }
+
+    /// Sorts the error response of a Helix request by its status
+    pub fn from_helix(err: ErrorResponse) -> ApiError {
+        match err.status {
+            400 => ApiError::BadRequest(err),
+            403 => ApiError::Forbidden(err),
+            _ => ApiError::TwitchError(err),
+        }
+    }
+
+    /// The HTTP status of an error returned by Twitch
+    ///
+    /// Match on this rather than on the variant, Helix errors
+    /// are split into `BadRequest`, `Forbidden` and `TwitchError`.
+    pub fn status(&self) -> Option<i32> {
+        match *self {
+            ApiError::TwitchError(ref err)
+            | ApiError::BadRequest(ref err)
+            | ApiError::Forbidden(ref err) => Some(err.status),
+            _ => None,
+        }
+    }
+
+    pub fn invalid_request(description: &str) -> ApiError {
+        ApiError::InvalidRequest(InvalidRequest {
+            description: description.to_owned(),
+        })
+    }
 }
 
 impl Error for ApiError {
@@ -70,7 +132,13 @@ impl Error for ApiError {
             ApiError::IoError(ref err) => err.description(),
             ApiError::ParseError(ref err) => err.description(),
             ApiError::TwitchError(ref err) => &err.error,
+            ApiError::BadRequest(ref err) => &err.error,
+            ApiError::Forbidden(ref err) => &err.error,
             ApiError::EmptyResponse(_) => "EmptyResponse",
+            ApiError::Timeout(_) => "Timeout",
+            ApiError::AuthError(ref err) => &err.error,
+            ApiError::MissingScope(_) => "MissingScope",
+            ApiError::InvalidRequest(ref err) => &err.description,
         }
     }
 
@@ -80,7 +148,13 @@ impl Error for ApiError {
             ApiError::IoError(ref err) => err as &Error,
             ApiError::ParseError(ref err) => err as &Error,
             ApiError::TwitchError(ref err) => err as &Error,
+            ApiError::BadRequest(ref err) => err as &Error,
+            ApiError::Forbidden(ref err) => err as &Error,
             ApiError::EmptyResponse(ref err) => err as &Error,
+            ApiError::Timeout(ref err) => err as &Error,
+            ApiError::AuthError(ref err) => err as &Error,
+            ApiError::MissingScope(ref err) => err as &Error,
+            ApiError::InvalidRequest(ref err) => err as &Error,
         })
     }
 }
@@ -92,7 +166,13 @@ impl fmt::Display for ApiError {
             ApiError::IoError(ref err) => fmt::Display::fmt(err, f),
             ApiError::ParseError(ref err) => fmt::Display::fmt(err, f),
             ApiError::TwitchError(ref err) => fmt::Display::fmt(err, f),
+            ApiError::BadRequest(ref err) => fmt::Display::fmt(err, f),
+            ApiError::Forbidden(ref err) => fmt::Display::fmt(err, f),
             ApiError::EmptyResponse(ref err) => fmt::Display::fmt(err, f),
+            ApiError::Timeout(ref err) => fmt::Display::fmt(err, f),
+            ApiError::AuthError(ref err) => fmt::Display::fmt(err, f),
+            ApiError::MissingScope(ref err) => fmt::Display::fmt(err, f),
+            ApiError::InvalidRequest(ref err) => fmt::Display::fmt(err, f),
         }
     }
 }
@@ -102,6 +182,7 @@ impl fmt::Display for ApiError {
 ///////////////////////////////////////
 #[derive(Deserialize, Debug)]
 pub struct ErrorResponse {
+    #[serde(default)]
     pub error: String,
     pub status: i32,
     pub message: String,
@@ -155,6 +236,107 @@ impl Error for EmptyResponse {
     }
 }
 
+///////////////////////////////////////
+// Timeout
+///////////////////////////////////////
+#[derive(Debug)]
+pub struct Timeout {}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Timeout")
+    }
+}
+
+impl Error for Timeout {
+    fn description(&self) -> &str {
+        "Timeout"
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        None
+    }
+}
+
+///////////////////////////////////////
+// AuthError
+///////////////////////////////////////
+#[derive(Debug)]
+pub struct AuthError {
+    pub error: String,
+    pub description: String,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AuthError: (Error: {}, Description: {})",
+            self.error, self.description
+        )
+    }
+}
+
+impl Error for AuthError {
+    fn description(&self) -> &str {
+        &self.error
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        None
+    }
+}
+
+///////////////////////////////////////
+// MissingScope
+///////////////////////////////////////
+#[derive(Debug)]
+pub struct MissingScope {
+    pub missing: ScopeSet,
+}
+
+impl fmt::Display for MissingScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MissingScope: (Scopes: {})", self.missing)
+    }
+}
+
+impl Error for MissingScope {
+    fn description(&self) -> &str {
+        "MissingScope"
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        None
+    }
+}
+
+///////////////////////////////////////
+// InvalidRequest
+///////////////////////////////////////
+/// A request rejected before sending it, because Twitch
+/// would not accept its parameters
+#[derive(Debug)]
+pub struct InvalidRequest {
+    pub description: String,
+}
+
+impl fmt::Display for InvalidRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "InvalidRequest: (Description: {})", self.description)
+    }
+}
+
+impl Error for InvalidRequest {
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        None
+    }
+}
+
 macro_rules! next_result {
     ($obj:ident, $url:expr, $serde:ty, $lst:ident) => {{
         let mut values_exist = false;
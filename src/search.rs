@@ -20,13 +20,20 @@ extern crate serde_json;
 extern crate urlparse;
 
 use std;
+use std::collections::{HashMap, VecDeque};
 use std::io::Write;
 
 use self::urlparse::quote;
 
+use serde::de;
+
 use super::channels::Channel;
 use super::games::Game;
-use super::response::TwitchResult;
+use super::helix;
+use super::helix::search::{self, SearchChannel};
+use super::helix::streams::{self, StreamFilter};
+use super::helix::HelixIterator;
+use super::response::{ApiError, TwitchResult};
 use super::streams::Stream;
 use super::TwitchClient;
 
@@ -38,6 +45,7 @@ use super::TwitchClient;
 ///
 /// #### Authentication: `None`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::search::channels`")]
 pub fn channels<'c>(c: &'c TwitchClient, query: &str) -> TwitchResult<SearchChannelIterator<'c>> {
     let iter = SearchChannelIterator {
         client: c,
@@ -55,6 +63,7 @@ pub fn channels<'c>(c: &'c TwitchClient, query: &str) -> TwitchResult<SearchChan
 ///
 /// #### Authentication: `None`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::search::categories`")]
 pub fn games<'c>(
     c: &'c TwitchClient,
     query: &str,
@@ -70,11 +79,20 @@ pub fn games<'c>(
     Ok(iter)
 }
 
-/// Searches for streams based on a specified query parameter
+/// Searches for live streams based on a specified query parameter
 ///
-/// A stream is returned if the query parameter is
-/// matched entirely or partially, in the channel
-/// description or game name.
+/// Kraken has been shut down, so this goes through Helix
+/// `Search Channels` limited to live channels, and fills in
+/// the Kraken stream objects from Helix `Get Streams`.
+///
+/// Helix does not report every Kraken field, these are made up:
+/// the stream's `created_at` and the channel's `created_at` and
+/// `updated_at` are the time the stream started, `video_height`,
+/// `average_fps`, `delay`, `followers` and `views` are zero, and
+/// `is_playlist` and `partner` are false.
+///
+/// Helix cannot filter by protocol, so passing one fails with
+/// `ApiError::InvalidRequest`.
 ///
 /// #### Authentication: `None`
 ///
@@ -83,12 +101,15 @@ pub fn streams<'c>(
     query: &str,
     protocol: Option<Protocol>,
 ) -> TwitchResult<SearchStreamIterator<'c>> {
+    if protocol.is_some() {
+        return Err(ApiError::invalid_request(
+            "Helix cannot filter streams by protocol",
+        ));
+    }
     let iter = SearchStreamIterator {
         client: c,
-        query: quote(query, b"").ok().unwrap(),
-        protocol: protocol,
-        cur: None,
-        offset: 0,
+        channels: r#try!(search::channels(c, query, true)),
+        cur: VecDeque::new(),
     };
     Ok(iter)
 }
@@ -154,15 +175,8 @@ impl<'c> Iterator for SearchGameIterator<'c> {
 ///////////////////////////////////////
 pub struct SearchStreamIterator<'c> {
     client: &'c TwitchClient,
-    query: String,
-    protocol: Option<Protocol>,
-    cur: Option<SerdeSearchStreams>,
-    offset: i32,
-}
-
-#[derive(Deserialize, Debug)]
-struct SerdeSearchStreams {
-    streams: Vec<Stream>,
+    channels: HelixIterator<'c, SearchChannel>,
+    cur: VecDeque<Stream>,
 }
 
 pub enum Protocol {
@@ -170,23 +184,105 @@ pub enum Protocol {
     RTMP,
 }
 
+impl<'c> SearchStreamIterator<'c> {
+    /// Looks up the streams of a page of live channels
+    fn live_streams(&self, channels: Vec<SearchChannel>) -> TwitchResult<Vec<Stream>> {
+        let filter = StreamFilter {
+            user_ids: channels.iter().map(|ch| ch.id.clone()).collect(),
+            ..StreamFilter::default()
+        };
+        let mut live = r#try!(r#try!(streams::get(self.client, &filter))
+            .first(filter.user_ids.len())
+            .all());
+
+        // keep the search order, dropping channels that went offline
+        let mut streams = Vec::with_capacity(live.len());
+        for ch in channels {
+            if let Some(i) = live.iter().position(|s| s.user_id == ch.id) {
+                streams.push(r#try!(kraken_stream(ch, live.swap_remove(i))));
+            }
+        }
+        Ok(streams)
+    }
+}
+
 impl<'c> Iterator for SearchStreamIterator<'c> {
     type Item = Stream;
 
     fn next(&mut self) -> Option<Stream> {
-        let mut path = String::from(format!(
-            "/search/streams?query={}&limit=100&offset={}",
-            self.query, self.offset
-        ));
-        path = match self.protocol {
-            Some(Protocol::HLS) => path + "&hls=true",
-            Some(Protocol::RTMP) => path + "&hls=false",
-            None => path,
-        };
-        next_result!(self, &path, SerdeSearchStreams, streams)
+        while self.cur.is_empty() {
+            let page = match self.channels.next_page() {
+                Ok(Some(page)) => page,
+                Ok(None) => return None,
+                Err(r) => {
+                    writeln!(&mut std::io::stderr(), "SearchStreamIterator Error: {}", r).unwrap();
+                    return None;
+                }
+            };
+            match self.live_streams(page) {
+                Ok(streams) => self.cur.extend(streams),
+                Err(r) => {
+                    writeln!(&mut std::io::stderr(), "SearchStreamIterator Error: {}", r).unwrap();
+                    return None;
+                }
+            }
+        }
+        self.cur.pop_front()
     }
 }
 
+fn kraken_stream(ch: SearchChannel, s: helix::streams::Stream) -> TwitchResult<Stream> {
+    let mut preview = HashMap::new();
+    preview.insert(String::from("small"), s.thumbnail(80, 45));
+    preview.insert(String::from("medium"), s.thumbnail(320, 180));
+    preview.insert(String::from("large"), s.thumbnail(640, 360));
+    preview.insert(String::from("template"), s.thumbnail_url.clone());
+
+    Ok(Stream {
+        id: r#try!(kraken_id(&s.id)),
+        game: s.game_name,
+        viewers: s.viewer_count as i32,
+        video_height: 0,
+        average_fps: 0,
+        delay: 0,
+        created_at: s.started_at,
+        is_playlist: false,
+        preview: preview,
+        channel: Channel {
+            id: r#try!(kraken_id(&ch.id)),
+            broadcaster_language: ch.broadcaster_language,
+            created_at: s.started_at,
+            display_name: ch.display_name,
+            email: None,
+            followers: 0,
+            game: ch.game_name,
+            language: s.language,
+            logo: ch.thumbnail_url,
+            mature: Some(s.is_mature),
+            url: format!("https://www.twitch.tv/{}", ch.broadcaster_login),
+            name: ch.broadcaster_login,
+            partner: false,
+            profile_banner: None,
+            profile_banner_background_color: None,
+            status: ch.title,
+            stream_key: None,
+            updated_at: s.started_at,
+            video_banner: None,
+            views: 0,
+        },
+    })
+}
+
+/// Kraken IDs are numbers, Helix sends them as strings
+fn kraken_id(id: &str) -> TwitchResult<i64> {
+    id.parse().map_err(|_| {
+        ApiError::from(<serde_json::Error as de::Error>::custom(format!(
+            "invalid ID {}",
+            id
+        )))
+    })
+}
+
 ///////////////////////////////////////
 // TESTS
 ///////////////////////////////////////
@@ -197,6 +293,7 @@ mod tests {
     use super::super::tests::CLIENTID;
 
     #[test]
+    #[allow(deprecated)]
     fn channels() {
         let c = new(String::from(CLIENTID));
 
@@ -210,6 +307,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn games() {
         let c = new(String::from(CLIENTID));
 
@@ -234,4 +332,18 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn streams_offline() {
+        let c = new(String::from(CLIENTID));
+
+        match super::streams(&c, "twitch", Some(super::Protocol::HLS)) {
+            Err(response::ApiError::InvalidRequest(_)) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+        assert_eq!(super::kraken_id("23161357").unwrap(), 23161357);
+        match super::kraken_id("") {
+            Err(response::ApiError::ParseError(_)) => (),
+            r => panic!("{:?}", r),
+        }
+    }
 }
@@ -25,6 +25,7 @@ use std::io::Write;
 
 use self::chrono::prelude::*;
 
+use super::auth::Scope;
 use super::channels::Channel;
 use super::response::TwitchResult;
 use super::TwitchClient;
@@ -96,13 +97,15 @@ pub fn featured<'c>(c: &'c TwitchClient) -> TwitchResult<FeaturedIterator<'c>> {
 ///
 /// #### Authentication: `user_read`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::streams::followed`")]
 pub fn followed(c: &TwitchClient) -> TwitchResult<FollowedStreams> {
+    r#try!(c.require_scopes(&[Scope::UserRead]));
     let mut lst = Vec::new();
     let mut r = r#try!(c.get::<FollowedStreams>("/streams/followed?limit=100"));
     lst.append(&mut r._streams);
     while let Some(cursor) = r._cursor {
         r = r#try!(
-            c.get::<FollowedStreams>(&format!("/streams/followsed?cursor={}&limit=100", cursor))
+            c.get::<FollowedStreams>(&format!("/streams/followed?cursor={}&limit=100", cursor))
         );
         lst.append(&mut r._streams);
     }
@@ -360,6 +363,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn followed() {
         let mut c = new(String::from(CLIENTID));
         c.set_oauth_token(TOKEN);
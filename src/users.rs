@@ -20,6 +20,7 @@ extern crate serde_json;
 
 use self::chrono::prelude::*;
 
+use super::auth::Scope;
 use super::channels::Channel;
 use super::chat::EmotesBySet;
 use super::response::{ApiError, TwitchResult};
@@ -35,6 +36,7 @@ use std::io::Write;
 /// #### Authentication: `user_read`
 ///
 pub fn get(c: &TwitchClient) -> TwitchResult<User> {
+    r#try!(c.require_scopes(&[Scope::UserRead]));
     let r = r#try!(c.get::<User>("/user"));
     Ok(r)
 }
@@ -57,19 +59,22 @@ pub fn get_by_id(c: &TwitchClient, user_id: &str) -> TwitchResult<User> {
 /// #### Authentication: `user_subscriptions`
 ///
 pub fn emotes(c: &TwitchClient, user_id: &str) -> TwitchResult<EmotesBySet> {
+    r#try!(c.require_scopes(&[Scope::UserSubscriptions]));
     let r = r#try!(c.get::<EmotesBySet>(&format!("/users/{}/emotes", user_id)));
     Ok(r)
 }
 
 /// Checks if a specified user is subscribed to a specified channel
 ///
-/// #### Authentication: `user_subscription`
+/// #### Authentication: `user_subscriptions`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::subscriptions::check`")]
 pub fn subscription(
     c: &TwitchClient,
     user_id: &str,
     channel_id: &str,
 ) -> TwitchResult<UserSubFollow> {
+    r#try!(c.require_scopes(&[Scope::UserSubscriptions]));
     let r =
         r#try!(c.get::<UserSubFollow>(&format!("/users/{}/subscriptions/{}", user_id, channel_id)));
     Ok(r)
@@ -80,6 +85,7 @@ pub fn subscription(
 ///
 /// #### Authentication: `None`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::channels::followed`")]
 pub fn following<'c>(c: &'c TwitchClient, user_id: &str) -> TwitchResult<UserFollowIterator<'c>> {
     let iter = UserFollowIterator {
         client: c,
@@ -96,6 +102,7 @@ pub fn following<'c>(c: &'c TwitchClient, user_id: &str) -> TwitchResult<UserFol
 ///
 /// #### Authentication: `None`
 ///
+#[deprecated(note = "Kraken has been shut down, use `helix::channels::followed`")]
 pub fn is_following(
     c: &TwitchClient,
     user_id: &str,
@@ -124,12 +131,14 @@ pub fn is_following(
 ///
 /// #### Authentication: `user_follows_edit`
 ///
+#[deprecated(note = "Kraken has been shut down, Helix has no endpoint to follow channels")]
 pub fn follow(
     c: &TwitchClient,
     user_id: &str,
     chan_id: &str,
     notifications: bool,
 ) -> TwitchResult<UserSubFollow> {
+    r#try!(c.require_scopes(&[Scope::UserFollowsEdit]));
     let mut data: HashMap<String, bool> = HashMap::new();
     data.insert("notifications".to_owned(), notifications);
     let r = r#try!(c.put::<HashMap<String, bool>, UserSubFollow>(
@@ -143,7 +152,9 @@ pub fn follow(
 ///
 /// #### Authentication: `user_follows_edit`
 ///
+#[deprecated(note = "Kraken has been shut down, Helix has no endpoint to follow channels")]
 pub fn unfollow(c: &TwitchClient, user_id: &str, chan_id: &str) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::UserFollowsEdit]));
     let r = c.delete::<()>(&format!("/users/{}/follows/channels/{}", user_id, chan_id));
     match r {
         Ok(_) => Ok(assert!(false)), // this should never happen
@@ -159,6 +170,7 @@ pub fn unfollow(c: &TwitchClient, user_id: &str, chan_id: &str) -> TwitchResult<
 /// #### Authentication: `user_blocks_read`
 ///
 pub fn blocking<'c>(c: &'c TwitchClient, user_id: &str) -> TwitchResult<UserBlockIterator<'c>> {
+    r#try!(c.require_scopes(&[Scope::UserBlocksRead]));
     let iter = UserBlockIterator {
         client: c,
         user_id: String::from(user_id),
@@ -174,6 +186,7 @@ pub fn blocking<'c>(c: &'c TwitchClient, user_id: &str) -> TwitchResult<UserBloc
 /// #### Authentication: `user_blocks_edit`
 ///
 pub fn block(c: &TwitchClient, src_user_id: &str, tgt_user_id: &str) -> TwitchResult<UserBlock> {
+    r#try!(c.require_scopes(&[Scope::UserBlocksEdit]));
     let r = r#try!(c.put::<Value, UserBlock>(
         &format!("/users/{}/blocks/{}", src_user_id, tgt_user_id),
         &Value::Null
@@ -191,6 +204,7 @@ pub fn block(c: &TwitchClient, src_user_id: &str, tgt_user_id: &str) -> TwitchRe
 /// #### Authentication: `user_blocks_edit`
 ///
 pub fn unblock(c: &TwitchClient, src_user_id: &str, tgt_user_id: &str) -> TwitchResult<()> {
+    r#try!(c.require_scopes(&[Scope::UserBlocksEdit]));
     let r = c.delete::<()>(&format!("/users/{}/blocks/{}", src_user_id, tgt_user_id));
     match r {
         Ok(_) => Ok(assert!(false)), // this should never happen
@@ -301,6 +315,7 @@ mod tests {
     use super::super::tests::{CHANID, CLIENTID, TESTCH, TOKEN};
 
     #[test]
+    #[allow(deprecated)]
     fn user() {
         let mut c = new(String::from(CLIENTID));
         c.set_oauth_token(TOKEN);
@@ -20,6 +20,7 @@ extern crate serde_json;
 
 use self::chrono::prelude::*;
 
+use super::auth::Scope;
 use super::response::TwitchResult;
 use super::TwitchClient;
 
@@ -67,6 +68,7 @@ pub fn top<'c>(
 /// #### Authentication: `user_read`
 ///
 pub fn followed<'c>(c: &'c TwitchClient) -> TwitchResult<FollowedVideoIterator<'c>> {
+    r#try!(c.require_scopes(&[Scope::UserRead]));
     let iter = FollowedVideoIterator {
         client: c,
         cur: None,